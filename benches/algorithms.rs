@@ -0,0 +1,174 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use clap::ValueEnum;
+use criterion::measurement::{Measurement, ValueFormatter};
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion, Throughput};
+
+use pathfinding_algorithms::algorithms::AlgorithmType;
+use pathfinding_algorithms::field::Field;
+use pathfinding_algorithms::headless::search;
+use pathfinding_algorithms::settings::Settings;
+
+const SIZES: [u16; 3] = [32, 64, 128];
+const DENSITIES: [f64; 2] = [0.1, 0.3];
+const SEED: u64 = 42;
+
+//Counts live heap bytes, so the peak memory of a search can be measured
+struct CountingAllocator;
+
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let pointer = System.alloc(layout);
+        if !pointer.is_null() {
+            let allocated = ALLOCATED.fetch_add(layout.size(), Ordering::Relaxed) + layout.size();
+            PEAK.fetch_max(allocated, Ordering::Relaxed);
+        }
+        pointer
+    }
+
+    unsafe fn dealloc(&self, pointer: *mut u8, layout: Layout) {
+        System.dealloc(pointer, layout);
+        ALLOCATED.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+//Peak of live heap bytes above the level at the start of the measurement
+struct PeakMemory;
+
+impl Measurement for PeakMemory {
+    type Intermediate = usize;
+    type Value = usize;
+
+    fn start(&self) -> usize {
+        let allocated = ALLOCATED.load(Ordering::Relaxed);
+        PEAK.store(allocated, Ordering::Relaxed);
+        allocated
+    }
+
+    fn end(&self, allocated: usize) -> usize {
+        PEAK.load(Ordering::Relaxed) - allocated
+    }
+
+    fn add(&self, v1: &usize, v2: &usize) -> usize {
+        v1 + v2
+    }
+
+    fn zero(&self) -> usize {
+        0
+    }
+
+    fn to_f64(&self, value: &usize) -> f64 {
+        *value as f64
+    }
+
+    fn formatter(&self) -> &dyn ValueFormatter {
+        &BytesFormatter
+    }
+}
+
+struct BytesFormatter;
+
+impl ValueFormatter for BytesFormatter {
+    fn scale_values(&self, typical_value: f64, values: &mut [f64]) -> &'static str {
+        let (factor, unit) = if typical_value < 1024.0 {
+            (1.0, "B")
+        } else if typical_value < 1024.0 * 1024.0 {
+            (1024.0, "KiB")
+        } else {
+            (1024.0 * 1024.0, "MiB")
+        };
+        for value in values.iter_mut() {
+            *value /= factor;
+        }
+        unit
+    }
+
+    fn scale_throughputs(&self, _typical_value: f64, throughput: &Throughput, values: &mut [f64]) -> &'static str {
+        // bytes per cell
+        if let Throughput::Elements(cells) = throughput {
+            for value in values.iter_mut() {
+                *value /= *cells as f64;
+            }
+        }
+        "B/cell"
+    }
+
+    fn scale_for_machines(&self, _values: &mut [f64]) -> &'static str {
+        "B"
+    }
+}
+
+//Fixed seeded maps, repaired so every algorithm searches until the end is found
+fn maps() -> Vec<(String, Settings, Field)> {
+    let mut maps = Vec::new();
+    for size in SIZES {
+        for density in DENSITIES {
+            let mut settings = Settings::new(1, size, size);
+            settings.seed = SEED;
+            settings.wall_density = density;
+            settings.ensure_solvable = true;
+            let field = Field::generate(&settings);
+            maps.push((format!("{}x{}/{}", size, size, density), settings, field));
+        }
+    }
+    maps
+}
+
+fn search_time(criterion: &mut Criterion) {
+    let maps = maps();
+    for algorithm in AlgorithmType::value_variants() {
+        let mut group = criterion.benchmark_group(format!("time/{}", algorithm.name()));
+        group.sample_size(20);
+        for (name, settings, field) in maps.iter() {
+            group.throughput(Throughput::Elements(field.columns() as u64 * field.rows() as u64));
+            group.bench_with_input(BenchmarkId::from_parameter(name), field, |bencher, field| {
+                bencher.iter_batched(
+                    || field.duplicate(),
+                    |field| search(settings, *algorithm, field),
+                    BatchSize::LargeInput,
+                );
+            });
+        }
+        group.finish();
+    }
+}
+
+fn search_memory(criterion: &mut Criterion<PeakMemory>) {
+    let maps = maps();
+    for algorithm in AlgorithmType::value_variants() {
+        let mut group = criterion.benchmark_group(format!("memory/{}", algorithm.name()));
+        group.sample_size(10);
+        for (name, settings, field) in maps.iter() {
+            group.throughput(Throughput::Elements(field.columns() as u64 * field.rows() as u64));
+            group.bench_with_input(BenchmarkId::from_parameter(name), field, |bencher, field| {
+                bencher.iter_batched(
+                    || field.duplicate(),
+                    |field| search(settings, *algorithm, field),
+                    BatchSize::PerIteration,
+                );
+            });
+        }
+        group.finish();
+    }
+}
+
+criterion_group!(time_benches, search_time);
+criterion_group! {
+    name = memory_benches;
+    // memory of a search does not vary, its density plots can not be built
+    config = Criterion::default()
+        .with_measurement(PeakMemory)
+        .without_plots()
+        .warm_up_time(Duration::from_millis(500))
+        .measurement_time(Duration::from_secs(1));
+    targets = search_memory
+}
+criterion_main!(time_benches, memory_benches);
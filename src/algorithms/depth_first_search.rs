@@ -1,74 +1,50 @@
-use std::collections::{HashMap, VecDeque};
-use std::sync::Arc;
-use std::sync::atomic::{AtomicBool, Ordering};
-
-use crate::algorithms::{colorize_path, Algorithm};
-use crate::cell::{CellState, Tile};
-use crate::state::SharedState;
-
-pub struct DFS(pub Arc<AtomicBool>);
-
-impl Algorithm for DFS {
-    fn search(&self, state: SharedState) {
-        let mut reachable_cells: VecDeque<Tile> = VecDeque::new();
-        let mut visited_cells: Vec<Tile> = Vec::new();
-        let mut ancestral_cells: HashMap<Tile, Tile> = HashMap::new();
-
-        let start_cell = state.get().field().get_cell(0, 0).clone();
-        start_cell.get().set_state(CellState::Start);
-
-        let end_coords_value = (state.get().field().cells.len() - 1) as u16;
-        let end_cell = state
-            .get()
-            .field()
-            .get_cell(end_coords_value, end_coords_value)
-            .clone();
-        end_cell.get().set_state(CellState::End);
-
-        reachable_cells.push_front(start_cell.clone());
-        visited_cells.push(start_cell.clone());
-
-        while let Some(current_cell) = reachable_cells.pop_front() {
-            if self.0.load(Ordering::Relaxed) {
-                break;
-            }
-            state.wait(25.0);
-            println!(
-                "r {} | v {} | a {}",
-                reachable_cells.len(),
-                visited_cells.len(),
-                ancestral_cells.len()
-            );
-            current_cell.get().set_state(CellState::Visited);
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use crate::algorithms::graph::{trace_path, Graph, SearchObserver, SearchResult};
+
+pub fn depth_first_search<G: Graph>(
+    graph: &G,
+    observer: &mut impl SearchObserver<G::Node>,
+) -> Option<SearchResult<G::Node>> {
+    let mut reachable_nodes: VecDeque<G::Node> = VecDeque::new();
+    let mut visited_nodes: HashSet<G::Node> = HashSet::new();
+    let mut ancestral_nodes: HashMap<G::Node, (G::Node, i32)> = HashMap::new();
+    let mut cost_so_far: HashMap<G::Node, i32> = HashMap::new();
+
+    let start = graph.start();
+    let goal = graph.goal();
+
+    reachable_nodes.push_front(start);
+    visited_nodes.insert(start);
+    cost_so_far.insert(start, 0);
+    observer.discover(start, None, Some(0), None, false);
+
+    while let Some(current) = reachable_nodes.pop_front() {
+        if observer.should_stop() {
+            break;
+        }
+        let current_cost = cost_so_far[&current];
+        observer.expand(current, reachable_nodes.len(), Some(current_cost), None);
 
-            if current_cell == end_cell {
-                let mut cell = end_cell.clone();
-                let mut path: Vec<Tile> = Vec::new();
+        if current == goal {
+            let result = trace_path(&ancestral_nodes, start, goal);
+            observer.found(&result.path, result.cost);
+            return Some(result);
+        }
 
-                while let Some(parent) = ancestral_cells.get(&cell) {
-                    path.push(cell.clone());
-                    cell = parent.clone();
-                }
-                path.push(start_cell.clone());
-                path.reverse();
-                println!("p {}", path.len());
-                colorize_path(path);
-                break;
+        for (neighbor, edge_cost) in graph.neighbors(current) {
+            if !visited_nodes.insert(neighbor) {
+                continue;
             }
 
-            let neighbor_cells = state
-                .get()
-                .field()
-                .check_cell_neighbors(current_cell.clone());
-            for neighbor_cell in neighbor_cells {
-                if visited_cells.contains(&neighbor_cell) {
-                    continue;
-                }
-
-                reachable_cells.push_front(neighbor_cell.clone());
-                visited_cells.push(neighbor_cell.clone());
-                ancestral_cells.insert(neighbor_cell.clone(), current_cell.clone());
-            }
+            reachable_nodes.push_front(neighbor);
+            let new_cost = current_cost + edge_cost;
+            cost_so_far.insert(neighbor, new_cost);
+            observer.discover(neighbor, Some(current), Some(new_cost), None, false);
+            ancestral_nodes.insert(neighbor, (current, edge_cost));
         }
+
+        observer.close(current);
     }
+    None
 }
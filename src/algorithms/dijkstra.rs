@@ -1,97 +1,53 @@
 use std::cmp::Reverse;
-use std::collections::{BinaryHeap, HashMap};
-use std::sync::Arc;
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::collections::{BinaryHeap, HashMap, HashSet};
 
-use crate::algorithms::{colorize_path, Algorithm, PriorityCell};
-use crate::cell::{CellState, Tile};
-use crate::state::SharedState;
+use crate::algorithms::graph::{trace_path, Graph, PriorityNode, SearchObserver, SearchResult};
 
-pub struct Dijkstra(pub Arc<AtomicBool>);
+pub fn dijkstra<G: Graph>(graph: &G, observer: &mut impl SearchObserver<G::Node>) -> Option<SearchResult<G::Node>> {
+    let mut reachable_nodes: BinaryHeap<Reverse<PriorityNode<G::Node>>> = BinaryHeap::new();
+    let mut closed_nodes: HashSet<G::Node> = HashSet::new();
+    let mut ancestral_nodes: HashMap<G::Node, (G::Node, i32)> = HashMap::new();
+    let mut cost_so_far: HashMap<G::Node, i32> = HashMap::new();
 
-impl Algorithm for Dijkstra {
-    fn search(&self, state: SharedState) {
-        let mut reachable_cells: BinaryHeap<Reverse<PriorityCell>> = BinaryHeap::new();
-        let mut visited_cells: Vec<Tile> = Vec::new();
-        let mut ancestral_cells: HashMap<Tile, Tile> = HashMap::new();
-        let mut cost_so_far: HashMap<Tile, i16> = HashMap::new();
+    let start = graph.start();
+    let goal = graph.goal();
 
-        let start_cell = state.get().field().get_cell(0, 0).clone();
-        start_cell.get().set_state(CellState::Start);
+    reachable_nodes.push(Reverse(PriorityNode { node: start, priority: 0 }));
+    cost_so_far.insert(start, 0);
+    observer.discover(start, None, Some(0), Some(0), false);
 
-        let end_coords_value = (state.get().field().cells.len() - 1) as u16;
-        let end_cell = state
-            .get()
-            .field()
-            .get_cell(end_coords_value, end_coords_value)
-            .clone();
-        end_cell.get().set_state(CellState::End);
-
-        let priority = start_cell.get().cost;
-        reachable_cells.push(Reverse(PriorityCell {
-            tile: start_cell.clone(),
-            cost: priority,
-        }));
-        visited_cells.push(start_cell.clone());
-
-        let start_cell_cost = start_cell.get().cost;
-        cost_so_far.insert(start_cell.clone(), start_cell_cost);
-
-        while let Some(current_cell) = reachable_cells.pop() {
-            if self.0.load(Ordering::Relaxed) {
-                break;
-            }
-            state.wait(25.0);
-            println!(
-                "r {} | v {} | a {}",
-                reachable_cells.len(),
-                visited_cells.len(),
-                ancestral_cells.len()
-            );
-            let current_cell = current_cell.0.tile.clone();
-            current_cell.get().set_state(CellState::Visited);
-
-            if current_cell == end_cell {
-                let mut cell = end_cell.clone();
-                let mut path: Vec<Tile> = Vec::new();
-
-                while let Some(parent) = ancestral_cells.get(&cell) {
-                    path.push(cell.clone());
-                    cell = parent.clone();
-                }
-                path.push(start_cell.clone());
-                path.reverse();
-                println!("p {}", path.len());
-                colorize_path(path);
-                break;
-            }
-
-            let neighbor_cells = state
-                .get()
-                .field()
-                .check_cell_neighbors(current_cell.clone());
-            for neighbor_cell in neighbor_cells {
-                if visited_cells.contains(&neighbor_cell) {
-                    continue;
-                }
+    while let Some(Reverse(PriorityNode { node: current, priority })) = reachable_nodes.pop() {
+        if observer.should_stop() {
+            break;
+        }
+        // the node was pushed again with a better cost and is already expanded
+        if closed_nodes.contains(&current) {
+            continue;
+        }
+        let current_cost = cost_so_far[&current];
+        observer.expand(current, reachable_nodes.len(), Some(current_cost), Some(priority));
 
-                let current_cell_cost = current_cell.get().cost;
-                let neighbor_cell_cost = neighbor_cell.get().cost;
-                let new_cost = current_cell_cost + neighbor_cell_cost;
-                if cost_so_far.get(&neighbor_cell.clone()).is_none()
-                    || new_cost < *cost_so_far.get(&neighbor_cell.clone()).unwrap()
-                {
-                    cost_so_far.insert(neighbor_cell.clone(), new_cost);
+        if current == goal {
+            let result = trace_path(&ancestral_nodes, start, goal);
+            observer.found(&result.path, result.cost);
+            return Some(result);
+        }
 
-                    let priority = new_cost;
-                    reachable_cells.push(Reverse(PriorityCell {
-                        tile: neighbor_cell.clone(),
-                        cost: priority,
-                    }));
-                    visited_cells.push(neighbor_cell.clone());
-                    ancestral_cells.insert(neighbor_cell.clone(), current_cell.clone());
-                }
+        for (neighbor, edge_cost) in graph.neighbors(current) {
+            let new_cost = current_cost + edge_cost;
+            if closed_nodes.contains(&neighbor)
+                || cost_so_far.get(&neighbor).is_some_and(|&cost| new_cost >= cost)
+            {
+                continue;
             }
+            cost_so_far.insert(neighbor, new_cost);
+            reachable_nodes.push(Reverse(PriorityNode { node: neighbor, priority: new_cost }));
+            observer.discover(neighbor, Some(current), Some(new_cost), Some(0), false);
+            ancestral_nodes.insert(neighbor, (current, edge_cost));
         }
+
+        observer.close(current);
+        closed_nodes.insert(current);
     }
+    None
 }
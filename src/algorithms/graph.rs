@@ -0,0 +1,91 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+//Anything the algorithms can search: grids, road networks, state spaces, navigation meshes
+pub trait Graph {
+    type Node: Copy + Eq + Hash;
+
+    fn start(&self) -> Self::Node;
+
+    fn goal(&self) -> Self::Node;
+
+    //Nodes reachable by one edge with costs of these edges
+    fn neighbors(&self, node: Self::Node) -> Vec<(Self::Node, i32)>;
+
+    //Estimated cost from the node to the goal, informed algorithms use 0 when there is none
+    fn heuristic(&self, _node: Self::Node) -> Option<i32> {
+        None
+    }
+}
+
+//Receives search events, e.g. to visualize a search. Every event does nothing by default
+pub trait SearchObserver<N> {
+    //Checked before every expansion, the search gives up when it is true
+    fn should_stop(&self) -> bool {
+        false
+    }
+
+    //Node taken from the frontier, frontier is the number of nodes left there
+    fn expand(&mut self, _node: N, _frontier: usize, _g: Option<i32>, _f: Option<i32>) {}
+
+    //Node put to the frontier, reopened when it was already expanded with a worse cost
+    fn discover(&mut self, _node: N, _parent: Option<N>, _g: Option<i32>, _h: Option<i32>, _reopened: bool) {}
+
+    //All neighbors of the node are discovered
+    fn close(&mut self, _node: N) {}
+
+    fn found(&mut self, _path: &[N], _cost: i32) {}
+}
+
+//Searches that are not watched
+impl<N> SearchObserver<N> for () {}
+
+pub struct SearchResult<N> {
+    pub path: Vec<N>,
+    // sum of edge costs along the path
+    pub cost: i32,
+}
+
+//Frontier entry ordered by its priority only
+pub struct PriorityNode<N> {
+    pub node: N,
+    pub priority: i32,
+}
+
+impl<N> Eq for PriorityNode<N> {}
+
+impl<N> PartialEq<Self> for PriorityNode<N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl<N> PartialOrd<Self> for PriorityNode<N> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N> Ord for PriorityNode<N> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.priority.cmp(&other.priority)
+    }
+}
+
+//Follow parents from the goal back to the start, parents hold the cost of the edge to the child
+pub fn trace_path<N: Copy + Eq + Hash>(parents: &HashMap<N, (N, i32)>, start: N, goal: N) -> SearchResult<N> {
+    let mut path = vec![goal];
+    let mut cost = 0;
+    let mut node = goal;
+    while node != start {
+        let Some(&(parent, edge_cost)) = parents.get(&node) else {
+            break;
+        };
+        path.push(parent);
+        cost += edge_cost;
+        node = parent;
+    }
+    path.reverse();
+    SearchResult { path, cost }
+}
@@ -1,85 +1,53 @@
 use std::cmp::Reverse;
-use std::collections::{BinaryHeap, HashMap};
-use std::sync::Arc;
-use std::sync::atomic::{AtomicBool, Ordering};
-
-use crate::algorithms::{colorize_path, heuristic_factor, Algorithm, PriorityCell};
-use crate::cell::{CellState, Tile};
-use crate::state::SharedState;
-
-pub struct GBFS(pub Arc<AtomicBool>);
-
-impl Algorithm for GBFS {
-    fn search(&self, state: SharedState) {
-        let mut reachable_cells: BinaryHeap<Reverse<PriorityCell>> = BinaryHeap::new();
-        let mut visited_cells: Vec<Tile> = Vec::new();
-        let mut ancestral_cells: HashMap<Tile, Tile> = HashMap::new();
-
-        let start_cell = state.get().field().get_cell(0, 0).clone();
-        start_cell.get().set_state(CellState::Start);
-
-        let end_coords_value = (state.get().field().cells.len() - 1) as u16;
-        let end_cell = state
-            .get()
-            .field()
-            .get_cell(end_coords_value, end_coords_value)
-            .clone();
-        end_cell.get().set_state(CellState::End);
-
-        let priority = heuristic_factor(start_cell.clone(), end_cell.clone());
-        reachable_cells.push(Reverse(PriorityCell {
-            tile: start_cell.clone(),
-            cost: priority,
-        }));
-        visited_cells.push(start_cell.clone());
-
-        while let Some(current_cell) = reachable_cells.pop() {
-            if self.0.load(Ordering::Relaxed) {
-                break;
-            }
-            state.wait(25.0);
-            println!(
-                "r {} | v {} | a {}",
-                reachable_cells.len(),
-                visited_cells.len(),
-                ancestral_cells.len()
-            );
-            let current_cell = current_cell.0.tile.clone();
-            current_cell.get().set_state(CellState::Visited);
-
-            if current_cell == end_cell {
-                let mut cell = end_cell.clone();
-                let mut path: Vec<Tile> = Vec::new();
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+use crate::algorithms::graph::{trace_path, Graph, PriorityNode, SearchObserver, SearchResult};
+
+pub fn greedy_best_first_search<G: Graph>(
+    graph: &G,
+    observer: &mut impl SearchObserver<G::Node>,
+) -> Option<SearchResult<G::Node>> {
+    let mut reachable_nodes: BinaryHeap<Reverse<PriorityNode<G::Node>>> = BinaryHeap::new();
+    let mut visited_nodes: HashSet<G::Node> = HashSet::new();
+    let mut ancestral_nodes: HashMap<G::Node, (G::Node, i32)> = HashMap::new();
+    let mut cost_so_far: HashMap<G::Node, i32> = HashMap::new();
+
+    let start = graph.start();
+    let goal = graph.goal();
+
+    let priority = graph.heuristic(start).unwrap_or(0);
+    reachable_nodes.push(Reverse(PriorityNode { node: start, priority }));
+    visited_nodes.insert(start);
+    cost_so_far.insert(start, 0);
+    observer.discover(start, None, Some(0), Some(priority), false);
+
+    while let Some(Reverse(PriorityNode { node: current, priority })) = reachable_nodes.pop() {
+        if observer.should_stop() {
+            break;
+        }
+        let current_cost = cost_so_far[&current];
+        observer.expand(current, reachable_nodes.len(), Some(current_cost), Some(priority));
 
-                while let Some(parent) = ancestral_cells.get(&cell) {
-                    path.push(cell.clone());
-                    cell = parent.clone();
-                }
+        if current == goal {
+            let result = trace_path(&ancestral_nodes, start, goal);
+            observer.found(&result.path, result.cost);
+            return Some(result);
+        }
 
-                path.push(start_cell.clone());
-                path.reverse();
-                println!("p {}", path.len());
-                colorize_path(path);
-                break;
+        for (neighbor, edge_cost) in graph.neighbors(current) {
+            if !visited_nodes.insert(neighbor) {
+                continue;
             }
 
-            let neighbor_cells = state
-                .get()
-                .field()
-                .check_cell_neighbors(current_cell.clone());
-            for neighbor_cell in neighbor_cells {
-                if visited_cells.contains(&neighbor_cell) {
-                    continue;
-                }
-
-                let priority = heuristic_factor(neighbor_cell.clone(), end_cell.clone());
-                reachable_cells.push(Reverse(PriorityCell {
-                    tile: neighbor_cell.clone(),
-                    cost: priority,
-                }));
-                visited_cells.push(neighbor_cell.clone());
-                ancestral_cells.insert(neighbor_cell.clone(), current_cell.clone());
-            }
+            let priority = graph.heuristic(neighbor).unwrap_or(0);
+            reachable_nodes.push(Reverse(PriorityNode { node: neighbor, priority }));
+            let new_cost = current_cost + edge_cost;
+            cost_so_far.insert(neighbor, new_cost);
+            observer.discover(neighbor, Some(current), Some(new_cost), Some(priority), false);
+            ancestral_nodes.insert(neighbor, (current, edge_cost));
         }
+
+        observer.close(current);
     }
+    None
 }
@@ -0,0 +1,127 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::algorithms::graph::{Graph, SearchObserver};
+use crate::algorithms::{colorize_path, label_cell, mark_cell, set_parent, Algorithm, AlgorithmType, Heuristic};
+use crate::cell::{CellCoordinates, CellState, Tile};
+use crate::state::SharedState;
+
+//Field of a search state as a graph: entering a cell costs the cell cost
+pub struct GridGraph {
+    state: SharedState,
+    start: CellCoordinates,
+    end: CellCoordinates,
+    heuristic: Heuristic,
+    // cheapest cost of entering a cell, the heuristic counts steps of this cost
+    min_cost: i32,
+}
+
+impl GridGraph {
+    pub fn new(state: SharedState) -> GridGraph {
+        let (start, end, heuristic, min_cost) = {
+            let mut state_guard = state.get();
+            let heuristic = state_guard.heuristic;
+            let field = state_guard.field();
+            (field.start, field.end, heuristic, field.min_passable_cost())
+        };
+        GridGraph { state, start, end, heuristic, min_cost }
+    }
+}
+
+impl Graph for GridGraph {
+    type Node = CellCoordinates;
+
+    fn start(&self) -> CellCoordinates {
+        self.start
+    }
+
+    fn goal(&self) -> CellCoordinates {
+        self.end
+    }
+
+    fn neighbors(&self, node: CellCoordinates) -> Vec<(CellCoordinates, i32)> {
+        let mut state = self.state.get();
+        let field = state.field();
+        field
+            .passable_neighbors(node)
+            .into_iter()
+            .map(|(x, y)| (CellCoordinates { x, y }, field.get_cell(x, y).get().cost as i32))
+            .collect()
+    }
+
+    fn heuristic(&self, node: CellCoordinates) -> Option<i32> {
+        let dx = node.x as i32 - self.end.x as i32;
+        let dy = node.y as i32 - self.end.y as i32;
+        // with free cells the estimate is 0 and A_STAR searches as DIJKSTRA
+        Some(self.heuristic.distance(dx, dy) * self.min_cost)
+    }
+}
+
+//Search of the grid of a state by any algorithm, stopping early when should_stop is set
+pub struct GridSearch {
+    pub algorithm_type: AlgorithmType,
+    pub should_stop: Arc<AtomicBool>,
+}
+
+impl Algorithm for GridSearch {
+    fn search(&self, state: SharedState) {
+        let graph = GridGraph::new(state.clone());
+        self.algorithm_type.search(&graph, &mut GridObserver::new(state, self.should_stop.clone()));
+    }
+}
+
+//Draws a grid search into the cells and statistics of the search state
+pub struct GridObserver {
+    state: SharedState,
+    should_stop: Arc<AtomicBool>,
+}
+
+impl GridObserver {
+    pub fn new(state: SharedState, should_stop: Arc<AtomicBool>) -> GridObserver {
+        state.get().field().mark_endpoints();
+        GridObserver { state, should_stop }
+    }
+
+    fn tile(&self, coordinates: CellCoordinates) -> Tile {
+        self.state.get().field().get_cell(coordinates.x, coordinates.y)
+    }
+}
+
+impl SearchObserver<CellCoordinates> for GridObserver {
+    fn should_stop(&self) -> bool {
+        self.should_stop.load(Ordering::SeqCst)
+    }
+
+    fn expand(&mut self, node: CellCoordinates, frontier: usize, g: Option<i32>, f: Option<i32>) {
+        self.state.wait(&self.should_stop);
+        let order = self.state.get().stats.expand(frontier, g, f);
+        let tile = self.tile(node);
+        tile.get().order = Some(order);
+        mark_cell(&tile, CellState::Current);
+    }
+
+    fn discover(
+        &mut self,
+        node: CellCoordinates,
+        parent: Option<CellCoordinates>,
+        g: Option<i32>,
+        h: Option<i32>,
+        reopened: bool,
+    ) {
+        let tile = self.tile(node);
+        label_cell(&tile, g, h);
+        if let Some(parent) = parent {
+            mark_cell(&tile, if reopened { CellState::Reopened } else { CellState::Frontier });
+            set_parent(&tile, &self.tile(parent));
+        }
+    }
+
+    fn close(&mut self, node: CellCoordinates) {
+        mark_cell(&self.tile(node), CellState::Closed);
+    }
+
+    fn found(&mut self, path: &[CellCoordinates], cost: i32) {
+        self.state.get().record_path(path, cost);
+        colorize_path(path.iter().map(|&coordinates| self.tile(coordinates)).collect());
+    }
+}
@@ -1,13 +1,31 @@
+use crate::algorithms::a_star::a_star;
+use crate::algorithms::breadth_first_search::breadth_first_search;
+use crate::algorithms::depth_first_search::depth_first_search;
+use crate::algorithms::dijkstra::dijkstra;
+use crate::algorithms::graph::{Graph, SearchObserver, SearchResult};
+use crate::algorithms::greedy_best_first_search::greedy_best_first_search;
+use crate::algorithms::grid::GridSearch;
+use crate::algorithms::registry::{AlgorithmInfo, ALGORITHMS, ALGORITHM_TYPES};
 use crate::cell::{CellState, Tile};
+use crate::field::Neighborhood;
 use crate::state::SharedState;
-use std::cmp::Ordering;
+use clap::builder::PossibleValue;
+use clap::ValueEnum;
+use serde::de::Error;
+use serde::{Deserialize, Deserializer};
+use std::sync::atomic::AtomicBool;
+use std::sync::{Arc, Mutex};
 pub mod a_star;
 pub mod breadth_first_search;
 pub mod depth_first_search;
 pub mod dijkstra;
+pub mod graph;
 pub mod greedy_best_first_search;
+pub mod grid;
+pub mod registry;
 
-#[derive(Copy, Clone)]
+//Key of an algorithm in the registry, everything else about it is in registry::ALGORITHMS
+#[derive(Copy, Clone, PartialEq, Debug)]
 pub enum AlgorithmType {
     BFS,
     DFS,
@@ -16,46 +34,132 @@ pub enum AlgorithmType {
     AStar,
 }
 
+impl AlgorithmType {
+    pub fn info(&self) -> &'static AlgorithmInfo {
+        ALGORITHMS
+            .iter()
+            .find(|info| info.algorithm_type == *self)
+            .expect("Every algorithm type is registered!")
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.info().name
+    }
+
+    //The search stops early when should_stop is set
+    pub fn create(&self, should_stop: Arc<AtomicBool>) -> Arc<Mutex<dyn Algorithm + Send + Sync>> {
+        Arc::new(Mutex::new(GridSearch { algorithm_type: *self, should_stop }))
+    }
+
+    //Search any graph, the observer sees every step. Generic functions can not be kept
+    //in the registry, so this is the only place that lists algorithms besides it
+    pub fn search<G: Graph>(
+        &self,
+        graph: &G,
+        observer: &mut impl SearchObserver<G::Node>,
+    ) -> Option<SearchResult<G::Node>> {
+        match self {
+            AlgorithmType::BFS => breadth_first_search(graph, observer),
+            AlgorithmType::DFS => depth_first_search(graph, observer),
+            AlgorithmType::GBFS => greedy_best_first_search(graph, observer),
+            AlgorithmType::Dijkstra => dijkstra(graph, observer),
+            AlgorithmType::AStar => a_star(graph, observer),
+        }
+    }
+}
+
+impl ValueEnum for AlgorithmType {
+    fn value_variants<'a>() -> &'a [Self] {
+        &ALGORITHM_TYPES
+    }
+
+    fn to_possible_value(&self) -> Option<PossibleValue> {
+        let info = self.info();
+        Some(PossibleValue::new(info.id).help(info.description))
+    }
+}
+
+impl<'de> Deserialize<'de> for AlgorithmType {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let id = String::deserialize(deserializer)?;
+        registry::find(&id).map(|info| info.algorithm_type).ok_or_else(|| {
+            let ids: Vec<&str> = ALGORITHMS.iter().map(|info| info.id).collect();
+            D::Error::custom(format!("unknown algorithm `{}`, expected one of {}", id, ids.join(", ")))
+        })
+    }
+}
+
+//Search of a grid state, run in its own thread
 pub trait Algorithm {
     fn search(&self, state: SharedState);
 }
 
-#[derive(Debug)]
-pub struct PriorityCell {
-    pub tile: Tile,
-    pub cost: i16,
+//Distance estimation used by informed algorithms (GBFS, A_STAR)
+#[derive(Copy, Clone, PartialEq, Deserialize, ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum Heuristic {
+    Manhattan,
+    Euclidean,
+    Chebyshev,
+    Octile,
 }
 
-impl Eq for PriorityCell {}
+impl Heuristic {
+    pub fn name(self) -> &'static str {
+        match self {
+            Heuristic::Manhattan => "manhattan",
+            Heuristic::Euclidean => "euclidean",
+            Heuristic::Chebyshev => "chebyshev",
+            Heuristic::Octile => "octile",
+        }
+    }
 
-impl PartialEq<Self> for PriorityCell {
-    fn eq(&self, other: &Self) -> bool {
-        self.tile == other.tile
+    //Never above the fewest steps to the goal. A diagonal step costs as much as a side one,
+    //so with eight neighbors only chebyshev counts steps, the others overestimate
+    pub fn is_admissible(self, neighborhood: Neighborhood) -> bool {
+        match neighborhood {
+            Neighborhood::Four => true,
+            Neighborhood::Eight => self == Heuristic::Chebyshev,
+        }
     }
-}
 
-impl PartialOrd<Self> for PriorityCell {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        self.cost.partial_cmp(&other.cost)
+    pub fn distance(self, dx: i32, dy: i32) -> i32 {
+        let (dx, dy) = (dx.abs(), dy.abs());
+        match self {
+            Heuristic::Manhattan => dx + dy,
+            Heuristic::Euclidean => ((dx * dx + dy * dy) as f64).sqrt().round() as i32,
+            Heuristic::Chebyshev => dx.max(dy),
+            Heuristic::Octile => {
+                let diagonal = dx.min(dy) as f64;
+                (dx.max(dy) as f64 + (std::f64::consts::SQRT_2 - 1.0) * diagonal).round() as i32
+            }
+        }
     }
 }
 
-impl Ord for PriorityCell {
-    fn cmp(&self, other: &Self) -> Ordering {
-        self.cost.cmp(&other.cost)
+//Search states never replace start and end cells
+pub fn mark_cell(tile: &Tile, state: CellState) {
+    let mut cell = tile.get();
+    if !matches!(cell.get_state(), CellState::Start | CellState::End) {
+        cell.set_state(state);
     }
 }
 
-pub fn heuristic_factor(a: Tile, b: Tile) -> i16 {
-    let ax = a.get().coordinates.x as i16;
-    let bx = b.get().coordinates.x as i16;
-    let ay = a.get().coordinates.y as i16;
-    let by = b.get().coordinates.y as i16;
-    return (ax - bx).abs() + (ay - by).abs();
+//Values shown in cells by the label overlay
+pub fn label_cell(tile: &Tile, g: Option<i32>, h: Option<i32>) {
+    let mut cell = tile.get();
+    cell.g = g;
+    cell.h = h;
+}
+
+//Parent pointers are drawn by the search tree overlay
+pub fn set_parent(child: &Tile, parent: &Tile) {
+    let coordinates = parent.get().coordinates;
+    child.get().parent = Some(coordinates);
 }
 
 pub fn colorize_path(path: Vec<Tile>) {
     path.iter()
-        .map(|tile| tile.get().set_state(CellState::Chosen))
+        .map(|tile| mark_cell(tile, CellState::Chosen))
         .count();
 }
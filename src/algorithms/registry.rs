@@ -0,0 +1,118 @@
+use crate::algorithms::AlgorithmType;
+
+#[derive(Copy, Clone, PartialEq)]
+pub enum Category {
+    // search without any estimate of the distance to the goal
+    Uninformed,
+    // search guided by a heuristic
+    Informed,
+    // search reusing its previous results when the map changes
+    Incremental,
+    // search with paths not bound to grid edges
+    AnyAngle,
+}
+
+impl Category {
+    //Menu order of the categories
+    pub const ALL: [Category; 4] = [
+        Category::Uninformed,
+        Category::Informed,
+        Category::Incremental,
+        Category::AnyAngle,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Category::Uninformed => "uninformed",
+            Category::Informed => "informed",
+            Category::Incremental => "incremental",
+            Category::AnyAngle => "any-angle",
+        }
+    }
+}
+
+//Settings an algorithm reads besides the map
+#[derive(Copy, Clone, PartialEq)]
+pub enum Parameter {
+    Heuristic,
+}
+
+impl Parameter {
+    pub fn name(self) -> &'static str {
+        match self {
+            Parameter::Heuristic => "heuristic",
+        }
+    }
+}
+
+pub struct AlgorithmInfo {
+    pub algorithm_type: AlgorithmType,
+    // name in the command line and the config file
+    pub id: &'static str,
+    pub name: &'static str,
+    pub description: &'static str,
+    pub category: Category,
+    pub parameters: &'static [Parameter],
+}
+
+//Every algorithm registers here once. The command line, the config file and the experiment
+//runner list algorithms in this order; the menu groups them by category and keeps it inside a group
+pub static ALGORITHMS: &[AlgorithmInfo] = &REGISTRY;
+
+const REGISTRY: [AlgorithmInfo; 5] = [
+    AlgorithmInfo {
+        algorithm_type: AlgorithmType::BFS,
+        id: "bfs",
+        name: "BFS",
+        description: "Breadth first search: fewest steps, costs are ignored",
+        category: Category::Uninformed,
+        parameters: &[],
+    },
+    AlgorithmInfo {
+        algorithm_type: AlgorithmType::DFS,
+        id: "dfs",
+        name: "DFS",
+        description: "Depth first search: follows one branch as deep as it goes",
+        category: Category::Uninformed,
+        parameters: &[],
+    },
+    AlgorithmInfo {
+        algorithm_type: AlgorithmType::GBFS,
+        id: "gbfs",
+        name: "GBFS",
+        description: "Greedy best first search: heads to the goal, fast but not optimal",
+        category: Category::Informed,
+        parameters: &[Parameter::Heuristic],
+    },
+    AlgorithmInfo {
+        algorithm_type: AlgorithmType::Dijkstra,
+        id: "dijkstra",
+        name: "DIJKSTRA",
+        description: "Dijkstra: cheapest cells first, optimal",
+        category: Category::Uninformed,
+        parameters: &[],
+    },
+    AlgorithmInfo {
+        algorithm_type: AlgorithmType::AStar,
+        id: "a-star",
+        name: "A_STAR",
+        description: "A*: Dijkstra guided by the heuristic, optimal only with an admissible one",
+        category: Category::Informed,
+        parameters: &[Parameter::Heuristic],
+    },
+];
+
+//Algorithm types in the registry order
+pub static ALGORITHM_TYPES: [AlgorithmType; REGISTRY.len()] = {
+    let mut algorithm_types = [AlgorithmType::BFS; REGISTRY.len()];
+    let mut n = 0;
+    while n < REGISTRY.len() {
+        algorithm_types[n] = REGISTRY[n].algorithm_type;
+        n += 1;
+    }
+    algorithm_types
+};
+
+pub fn find(id: &str) -> Option<&'static AlgorithmInfo> {
+    ALGORITHMS.iter().find(|info| info.id == id)
+}
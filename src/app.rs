@@ -1,170 +1,787 @@
+use std::collections::HashSet;
+use std::ops::Range;
 use std::sync::{Arc, Mutex};
 use std::sync::atomic::AtomicBool;
-use std::thread;
-use std::thread::JoinHandle;
 
-use piston_window::{clear, Context, G2d, Glyphs, MouseButton, rectangle, text, Transformed};
+use piston_window::{CharacterCache, clear, Context, ellipse, G2d, Glyphs, Line, MouseButton, Rectangle, rectangle, text, Transformed};
+use piston_window::types::Color;
 
+use crate::algorithms::registry::Parameter;
 use crate::algorithms::{Algorithm, AlgorithmType};
-use crate::algorithms::a_star::AStar;
-use crate::algorithms::breadth_first_search::BFS;
-use crate::algorithms::depth_first_search::DFS;
-use crate::algorithms::dijkstra::Dijkstra;
-use crate::algorithms::greedy_best_first_search::GBFS;
+use crate::camera::Camera;
 use crate::cell::{CellCoordinates, CellState};
-use crate::colors::{BLOCKED_CELL_COLOR, CHOSEN_CELL_COLOR, EMPTY_CELL_COLOR, EMPTY_FIELD_COLOR, END_CELL_COLOR, START_CELL_COLOR, VISITED_CELL_COLOR};
+use crate::colors::Theme;
 use crate::field::Field;
-use crate::settings::{Settings, Vec2f};
+use crate::run::SearchRun;
+use crate::scenario::SCENARIOS;
+use crate::settings::{CellLabel, Settings, Vec2f};
 use crate::state::{SharedState, State};
+use crate::validation::PathVerdict;
+
+const MINIMAP_MARGIN: f64 = 10.0;
+// the minimap takes at most this share of the window width and height
+const MINIMAP_MAX_SHARE: f64 = 0.25;
 
 struct Alg(Arc<Mutex<dyn Algorithm + Send + Sync>>);
 
 pub struct App {
-    pub pathfinder_handler: Option<JoinHandle<()>>,
-    pub should_stop: Arc<AtomicBool>,
+    run: Option<SearchRun>,
+    should_stop: Arc<AtomicBool>,
     algorithm: Alg,
+    algorithm_type: AlgorithmType,
     settings: Settings,
     state: SharedState,
     mouse_coordinates: Vec2f,
-    selected_cell: Option<CellCoordinates>,
+    camera: Camera,
+    // last mouse position while the field is dragged
+    drag_from: Option<Vec2f>,
+    // the camera follows the mouse while the minimap is dragged
+    minimap_drag: bool,
+    // check of the found path, made once the search is finished
+    verdict: Option<PathVerdict>,
+    // set when start and end are not connected, so the search is not started
+    reachable_region: Option<HashSet<CellCoordinates>>,
 }
 
 impl App {
     pub fn new(settings: Settings, algorithm_type: &AlgorithmType) -> App {
-        let mut field = Field::new(settings.cells_number);
-        field.make_noise();
-        field.set_prices();
+        App::with_field(settings, algorithm_type, Field::generate(&settings))
+    }
 
-        let state = SharedState::new(State::new(field, 1.0));
+    pub fn with_field(settings: Settings, algorithm_type: &AlgorithmType, field: Field) -> App {
+        let field_size = Vec2f {
+            raw_x: field.columns() as f64 * settings.cell_size.raw_x,
+            raw_y: field.rows() as f64 * settings.cell_size.raw_y,
+        };
+        let state = SharedState::new(State::new(field, &settings));
 
         let should_stop = Arc::new(AtomicBool::new(false));
         let should_stop_ref = Arc::clone(&should_stop);
 
-        let algorithm: Alg = match algorithm_type {
-            AlgorithmType::BFS => Alg(Arc::new(Mutex::new(BFS(should_stop_ref)))),
-            AlgorithmType::DFS => Alg(Arc::new(Mutex::new(DFS(should_stop_ref)))),
-            AlgorithmType::GBFS => Alg(Arc::new(Mutex::new(GBFS(should_stop_ref)))),
-            AlgorithmType::Dijkstra => Alg(Arc::new(Mutex::new(Dijkstra(should_stop_ref)))),
-            AlgorithmType::AStar => Alg(Arc::new(Mutex::new(AStar(should_stop_ref)))),
-        };
+        let algorithm = Alg(algorithm_type.create(should_stop_ref));
 
         App {
-            pathfinder_handler: None,
+            run: None,
             should_stop,
             algorithm,
+            algorithm_type: *algorithm_type,
             state,
             settings,
             mouse_coordinates: Vec2f {
                 raw_x: 0.0,
                 raw_y: 0.0,
             },
-            selected_cell: None,
+            camera: Camera::new(field_size, settings.window_size),
+            drag_from: None,
+            minimap_drag: false,
+            verdict: None,
+            reachable_region: None,
         }
     }
 
     pub fn start(&mut self) {
+        {
+            let mut state = self.state.get();
+            let field = state.field();
+            if !field.is_solvable() {
+                self.reachable_region = Some(field.reachable_from(field.start));
+                return;
+            }
+        }
+
         let a = Arc::clone(&self.algorithm.0);
         let s = self.state.clone();
-        let algorithm_thread = thread::Builder::new()
-            .name("algorithm".to_string())
-            .spawn(move || {
-                let b = a.lock().unwrap();
-                b.search(s);
-            })
-            .unwrap();
-        self.pathfinder_handler = Some(algorithm_thread);
+        let run = SearchRun::spawn("algorithm", self.algorithm_type.name(), Arc::clone(&self.should_stop), move || {
+            let b = a.lock().unwrap();
+            s.get().stats.start();
+            b.search(s.clone());
+            s.get().stats.finish();
+        });
+        self.run = Some(run);
+    }
+
+    //Search thread, taken to be stopped
+    pub fn take_run(&mut self) -> Option<SearchRun> {
+        self.run.take()
     }
 
     pub fn render(&mut self, context: Context, g2d: &mut G2d, glyphs: &mut Glyphs) {
-        clear(EMPTY_FIELD_COLOR, g2d);
+        clear(self.settings.theme.empty_field, g2d);
+        self.render_field(self.camera.apply(context), g2d, glyphs);
+        if self.reachable_region.is_some() {
+            self.render_no_path_message(context, g2d, glyphs);
+        }
+        if self.settings.show_hud {
+            self.render_hud(context, g2d, glyphs);
+        }
+        if self.is_minimap_visible() {
+            self.render_minimap(context, g2d);
+        }
+        self.update_verdict();
+        if let Some(verdict) = &self.verdict {
+            self.render_verdict(verdict, context, g2d, glyphs);
+        }
+        self.render_path_tooltip(context, g2d, glyphs);
+    }
 
-        for x in 0..self.settings.cells_number {
-            for y in 0..self.settings.cells_number {
+    //Draw the field without clearing the window, so it can be placed into a panel.
+    //Only cells seen by the camera are drawn
+    pub fn render_field(&mut self, context: Context, g2d: &mut G2d, glyphs: &mut Glyphs) {
+        let theme = self.settings.theme;
+        let (columns, rows) = self.field_size();
+        let (visible_x, visible_y) = self.visible_cells();
+        // scenario and loaded maps do not keep to the cost range of the settings
+        let cost_range = if self.settings.cost_gradient {
+            self.state.get().field().passable_cost_range()
+        } else {
+            (0, 0)
+        };
+        for x in visible_x {
+            for y in visible_y.clone() {
                 let cell = self.state.get().field().get_cell(x, y);
 
+                let cell_cost = cell.get().cost;
+                let label = match self.settings.cell_label {
+                    CellLabel::Cost => Some(cell_cost as i32),
+                    CellLabel::G => cell.get().g,
+                    CellLabel::H => cell.get().h,
+                    CellLabel::F => cell.get().f(),
+                    CellLabel::Order => cell.get().order.map(|order| order as i32),
+                };
+                let is_blocked = *cell.get().get_state() == CellState::Blocked;
+                let is_empty = *cell.get().get_state() == CellState::Empty;
                 let color: [f32; 4] = match cell.get().get_state() {
-                    CellState::Blocked => BLOCKED_CELL_COLOR,
-                    CellState::Visited => VISITED_CELL_COLOR,
-                    CellState::Chosen => CHOSEN_CELL_COLOR,
-                    CellState::Empty => EMPTY_CELL_COLOR,
-                    CellState::End => END_CELL_COLOR,
-                    CellState::Start => START_CELL_COLOR,
+                    CellState::Blocked => theme.blocked_cell,
+                    CellState::Frontier => theme.frontier_cell,
+                    CellState::Closed => theme.closed_cell,
+                    CellState::Current => theme.current_cell,
+                    CellState::Reopened => theme.reopened_cell,
+                    CellState::Chosen => theme.chosen_cell,
+                    CellState::Empty => theme.empty_cell,
+                    CellState::End => theme.end_cell,
+                    CellState::Start => theme.start_cell,
                 };
 
                 let cell_raw_x = (x as f64) * self.settings.cell_size.raw_x;
                 let cell_raw_y = (y as f64) * self.settings.cell_size.raw_y;
+                let cell_rectangle = [
+                    cell_raw_x,
+                    cell_raw_y,
+                    self.settings.cell_size.raw_x,
+                    self.settings.cell_size.raw_y,
+                ];
 
-                rectangle(
-                    color,
-                    [
-                        cell_raw_x,
-                        cell_raw_y,
-                        self.settings.cell_size.raw_x,
-                        self.settings.cell_size.raw_y,
-                    ],
-                    context.transform,
-                    g2d,
-                );
-
-                let transform = context.transform.trans(
-                    cell_raw_x + self.settings.cell_offset.raw_x,
-                    cell_raw_y + self.settings.cell_offset.raw_y,
-                );
-
-                text::Text::new_color(piston_window::color::BLACK, self.settings.font_size)
-                    .draw(
-                        &*cell.get().cost.to_string(),
-                        glyphs,
-                        &context.draw_state,
-                        transform,
+                // search states are drawn over the cost gradient
+                if self.settings.cost_gradient && !is_blocked {
+                    rectangle(
+                        theme.cost_gradient_color(cell_cost, cost_range),
+                        cell_rectangle,
+                        context.transform,
                         g2d,
-                    )
-                    .unwrap();
+                    );
+                    if !is_empty {
+                        rectangle(color, cell_rectangle, context.transform, g2d);
+                    }
+                } else {
+                    rectangle(color, cell_rectangle, context.transform, g2d);
+                }
+
+                if let Some(label) = label {
+                    self.render_label(&label.to_string(), cell_raw_x, cell_raw_y, context, g2d, glyphs);
+                }
             }
         }
 
-        for n in 1..self.settings.cells_number {
-            let border_width = 1.0;
-            //vertical lines
+        let border_width = 1.0;
+        let field_width = columns as f64 * self.settings.cell_size.raw_x;
+        let field_height = rows as f64 * self.settings.cell_size.raw_y;
+        //vertical lines
+        for n in 1..columns {
             rectangle(
-                BLOCKED_CELL_COLOR,
+                theme.grid_line,
                 [
                     (n as f64) * self.settings.cell_size.raw_x - border_width,
                     0.0,
                     border_width,
-                    self.settings.window_size.raw_y,
+                    field_height,
                 ],
                 context.transform,
                 g2d,
             );
-            //horizontal lines
+        }
+        //horizontal lines
+        for n in 1..rows {
             rectangle(
-                BLOCKED_CELL_COLOR,
+                theme.grid_line,
                 [
                     0.0,
                     (n as f64) * self.settings.cell_size.raw_y - border_width,
-                    self.settings.window_size.raw_x,
+                    field_width,
                     border_width,
                 ],
                 context.transform,
                 g2d,
             );
         }
+
+        if self.settings.show_parents {
+            self.render_parents(context, g2d);
+        }
+
+        self.render_path(context, g2d);
+
+        if let Some(reachable_region) = &self.reachable_region {
+            self.render_reachable_region(reachable_region, context, g2d);
+        }
+    }
+
+    //Field size in field pixels, before the camera zoom
+    pub fn field_pixel_size(&self) -> Vec2f {
+        let (columns, rows) = self.field_size();
+        Vec2f {
+            raw_x: columns as f64 * self.settings.cell_size.raw_x,
+            raw_y: rows as f64 * self.settings.cell_size.raw_y,
+        }
+    }
+
+    //Columns and rows of cells inside the window
+    fn visible_cells(&self) -> (Range<u16>, Range<u16>) {
+        let (columns, rows) = self.field_size();
+        let [x, y, width, height] = self.camera.viewport(self.settings.window_size);
+        let first = |position: f64, cell_size: f64, limit: u16| (position / cell_size).floor().clamp(0.0, limit as f64) as u16;
+        let last = |position: f64, cell_size: f64, limit: u16| (position / cell_size).ceil().clamp(0.0, limit as f64) as u16;
+        (
+            first(x, self.settings.cell_size.raw_x, columns)..last(x + width, self.settings.cell_size.raw_x, columns),
+            first(y, self.settings.cell_size.raw_y, rows)..last(y + height, self.settings.cell_size.raw_y, rows),
+        )
+    }
+
+    fn is_minimap_visible(&self) -> bool {
+        let [x, y, width, height] = self.camera.viewport(self.settings.window_size);
+        let field_size = self.field_pixel_size();
+        let sees_whole_field = x <= 0.0 && y <= 0.0 && x + width >= field_size.raw_x && y + height >= field_size.raw_y;
+        self.settings.show_minimap && !sees_whole_field
+    }
+
+    //Minimap rectangle in the bottom right corner, one pixel per cell, and the first column and row
+    //it shows. A field bigger than the minimap is scrolled to keep the camera view in its middle
+    fn minimap_layout(&self) -> ([f64; 4], [usize; 2]) {
+        let (columns, rows) = self.field_size();
+        let window_size = self.settings.window_size;
+        let width = (columns as f64).min((window_size.raw_x * MINIMAP_MAX_SHARE).floor());
+        let height = (rows as f64).min((window_size.raw_y * MINIMAP_MAX_SHARE).floor());
+        let [view_x, view_y, view_width, view_height] = self.camera.viewport(window_size);
+        let first = |view_center: f64, cell_size: f64, cells: u16, shown: f64| {
+            (view_center / cell_size - shown / 2.0).clamp(0.0, cells as f64 - shown) as usize
+        };
+        (
+            [
+                window_size.raw_x - width - MINIMAP_MARGIN,
+                window_size.raw_y - height - MINIMAP_MARGIN,
+                width,
+                height,
+            ],
+            [
+                first(view_x + view_width / 2.0, self.settings.cell_size.raw_x, columns, width),
+                first(view_y + view_height / 2.0, self.settings.cell_size.raw_y, rows, height),
+            ],
+        )
+    }
+
+    //Field around the camera with search states and the camera viewport
+    fn render_minimap(&self, context: Context, g2d: &mut G2d) {
+        let theme = self.settings.theme;
+        let ([minimap_x, minimap_y, width, height], [first_column, first_row]) = self.minimap_layout();
+        rectangle(theme.empty_cell, [minimap_x, minimap_y, width, height], context.transform, g2d);
+
+        let color_of = |state: &CellState| -> Option<Color> {
+            match state {
+                CellState::Empty => None,
+                CellState::Blocked => Some(theme.blocked_cell),
+                CellState::Frontier => Some(theme.frontier_cell),
+                CellState::Closed => Some(theme.closed_cell),
+                CellState::Current => Some(theme.current_cell),
+                CellState::Reopened => Some(theme.reopened_cell),
+                CellState::Chosen => Some(theme.path_line),
+                CellState::End => Some(theme.end_cell),
+                CellState::Start => Some(theme.start_cell),
+            }
+        };
+        {
+            let mut state = self.state.get();
+            let field = state.field();
+            for (x, column) in field.cells.iter().skip(first_column).take(width as usize).enumerate() {
+                let colors: Vec<Option<Color>> = column
+                    .iter()
+                    .skip(first_row)
+                    .take(height as usize)
+                    .map(|tile| color_of(tile.get().get_state()))
+                    .collect();
+                // cells of the same color in a row of a column are drawn as one rectangle
+                let mut run_start = 0;
+                for y in 1..=colors.len() {
+                    if y < colors.len() && colors[y] == colors[run_start] {
+                        continue;
+                    }
+                    if let Some(color) = colors[run_start] {
+                        rectangle(
+                            color,
+                            [
+                                minimap_x + x as f64,
+                                minimap_y + run_start as f64,
+                                1.0,
+                                (y - run_start) as f64,
+                            ],
+                            context.transform,
+                            g2d,
+                        );
+                    }
+                    run_start = y;
+                }
+            }
+        }
+
+        let [view_x, view_y, view_width, view_height] = self.camera.viewport(self.settings.window_size);
+        let left = minimap_x - first_column as f64;
+        let top = minimap_y - first_row as f64;
+        let (scale_x, scale_y) = (1.0 / self.settings.cell_size.raw_x, 1.0 / self.settings.cell_size.raw_y);
+        let view_left = (left + view_x * scale_x).max(minimap_x);
+        let view_top = (top + view_y * scale_y).max(minimap_y);
+        let view_right = (left + (view_x + view_width) * scale_x).min(minimap_x + width);
+        let view_bottom = (top + (view_y + view_height) * scale_y).min(minimap_y + height);
+        Rectangle::new_border(theme.grid_line, 1.0).draw(
+            [minimap_x, minimap_y, width, height],
+            &context.draw_state,
+            context.transform,
+            g2d,
+        );
+        // the view can be panned off the field and out of the minimap
+        if view_left < view_right && view_top < view_bottom {
+            Rectangle::new_border(theme.path_line, 1.0).draw(
+                [view_left, view_top, view_right - view_left, view_bottom - view_top],
+                &context.draw_state,
+                context.transform,
+                g2d,
+            );
+        }
+    }
+
+    //Center the camera on the field point under the mouse, if the mouse is over the minimap
+    fn jump_by_minimap(&mut self) -> bool {
+        if !self.is_minimap_visible() {
+            return false;
+        }
+        let ([minimap_x, minimap_y, width, height], [first_column, first_row]) = self.minimap_layout();
+        let x = self.mouse_coordinates.raw_x - minimap_x;
+        let y = self.mouse_coordinates.raw_y - minimap_y;
+        if !(0.0..width).contains(&x) || !(0.0..height).contains(&y) {
+            return false;
+        }
+        let point = Vec2f {
+            raw_x: (first_column as f64 + x) * self.settings.cell_size.raw_x,
+            raw_y: (first_row as f64 + y) * self.settings.cell_size.raw_y,
+        };
+        self.camera.center_on(point, self.settings.window_size);
+        true
+    }
+
+    //Cell under the mouse cursor, if the cursor is over the field
+    fn hovered_cell(&self) -> Option<CellCoordinates> {
+        let point = self.camera.field_point(self.mouse_coordinates);
+        let x = (point.raw_x / self.settings.cell_size.raw_x).floor();
+        let y = (point.raw_y / self.settings.cell_size.raw_y).floor();
+        let (columns, rows) = self.field_size();
+        if x < 0.0 || y < 0.0 || x >= columns as f64 || y >= rows as f64 {
+            return None;
+        }
+        Some(CellCoordinates { x: x as u16, y: y as u16 })
+    }
+
+    fn cell_center(&self, coordinates: CellCoordinates) -> [f64; 2] {
+        [
+            (coordinates.x as f64 + 0.5) * self.settings.cell_size.raw_x,
+            (coordinates.y as f64 + 0.5) * self.settings.cell_size.raw_y,
+        ]
+    }
+
+    //Found path as a line with direction arrows, start and end markers
+    fn render_path(&self, context: Context, g2d: &mut G2d) {
+        let path = self.state.get().path.clone();
+        let (Some(&start), Some(&end)) = (path.first(), path.last()) else {
+            return;
+        };
+
+        let cell_width = self.settings.cell_size.raw_x;
+        let path_line = Line::new(self.settings.theme.path_line, cell_width * 0.08);
+        for step in path.windows(2) {
+            let [from_x, from_y] = self.cell_center(step[0]);
+            let [to_x, to_y] = self.cell_center(step[1]);
+            path_line.draw([from_x, from_y, to_x, to_y], &context.draw_state, context.transform, g2d);
+            // arrow head in the middle of the step shows the direction
+            path_line.draw_arrow(
+                [from_x, from_y, (from_x + to_x) / 2.0, (from_y + to_y) / 2.0],
+                cell_width * 0.25,
+                &context.draw_state,
+                context.transform,
+                g2d,
+            );
+        }
+
+        let marker_size = cell_width * 0.5;
+        let [start_x, start_y] = self.cell_center(start);
+        ellipse(
+            self.settings.theme.path_line,
+            [start_x - marker_size / 2.0, start_y - marker_size / 2.0, marker_size, marker_size],
+            context.transform,
+            g2d,
+        );
+        let [end_x, end_y] = self.cell_center(end);
+        rectangle(
+            self.settings.theme.path_line,
+            [end_x - marker_size / 2.0, end_y - marker_size / 2.0, marker_size, marker_size],
+            context.transform,
+            g2d,
+        );
+    }
+
+    //Step number, cell cost and cost so far of the hovered path cell
+    fn render_path_tooltip(&self, context: Context, g2d: &mut G2d, glyphs: &mut Glyphs) {
+        let Some(hovered) = self.hovered_cell() else {
+            return;
+        };
+        let lines = {
+            let mut state = self.state.get();
+            let Some(step) = state.path.iter().position(|&coordinates| coordinates == hovered) else {
+                return;
+            };
+            let path = state.path.clone();
+            let field = state.field();
+            let cost_of = |coordinates: &CellCoordinates| field.get_cell(coordinates.x, coordinates.y).get().cost as i32;
+            let cumulative_cost: i32 = path[1..=step].iter().map(cost_of).sum();
+            [
+                format!("step {} of {}", step, path.len() - 1),
+                format!("cell ({}, {})", hovered.x, hovered.y),
+                format!("cell cost: {}", cost_of(&hovered)),
+                format!("total cost: {}", cumulative_cost),
+            ]
+        };
+
+        let font_size = self.settings.font_size;
+        let line_height = font_size as f64 * 1.4;
+        let padding = self.settings.cell_offset.raw_x;
+        let tooltip_width = lines
+            .iter()
+            .map(|line| glyphs.width(font_size, line).unwrap())
+            .fold(0.0, f64::max)
+            + padding * 2.0;
+        let tooltip_height = line_height * lines.len() as f64 + padding;
+        // keep the tooltip inside the window
+        let tooltip_x = (self.mouse_coordinates.raw_x + 15.0)
+            .min(self.settings.window_size.raw_x - tooltip_width);
+        let tooltip_y = (self.mouse_coordinates.raw_y + 15.0)
+            .min(self.settings.window_size.raw_y - tooltip_height);
+        rectangle(
+            self.settings.theme.hud_background,
+            [tooltip_x, tooltip_y, tooltip_width, tooltip_height],
+            context.transform,
+            g2d,
+        );
+        for (n, line) in lines.iter().enumerate() {
+            text::Text::new_color(self.settings.theme.text, font_size)
+                .draw(
+                    line,
+                    glyphs,
+                    &context.draw_state,
+                    context.transform.trans(tooltip_x + padding, tooltip_y + line_height * (n + 1) as f64),
+                    g2d,
+                )
+                .unwrap();
+        }
+    }
+
+    fn field_size(&self) -> (u16, u16) {
+        let mut state = self.state.get();
+        let field = state.field();
+        (field.columns(), field.rows())
+    }
+
+    //Search tree: an arrow from every reached cell to its parent
+    fn render_parents(&self, context: Context, g2d: &mut G2d) {
+        let cell_width = self.settings.cell_size.raw_x;
+        let arrow = Line::new(self.settings.theme.parent_arrow, 1.0);
+        let (visible_x, visible_y) = self.visible_cells();
+        for x in visible_x {
+            for y in visible_y.clone() {
+                let cell = self.state.get().field().get_cell(x, y);
+                let Some(parent) = cell.get().parent else {
+                    continue;
+                };
+                let [from_x, from_y] = self.cell_center(CellCoordinates { x, y });
+                let [to_x, to_y] = self.cell_center(parent);
+                // stop short of the parent center, so arrows of neighbors do not merge
+                arrow.draw_arrow(
+                    [
+                        from_x,
+                        from_y,
+                        from_x + (to_x - from_x) * 0.7,
+                        from_y + (to_y - from_y) * 0.7,
+                    ],
+                    cell_width * 0.2,
+                    &context.draw_state,
+                    context.transform,
+                    g2d,
+                );
+            }
+        }
+    }
+
+    //Long labels get a smaller font to fit into the cell
+    fn render_label(
+        &self,
+        label: &str,
+        cell_raw_x: f64,
+        cell_raw_y: f64,
+        context: Context,
+        g2d: &mut G2d,
+        glyphs: &mut Glyphs,
+    ) {
+        let mut font_size = self.settings.font_size;
+        let mut label_width = glyphs.width(font_size, label).unwrap();
+        let max_width = self.settings.cell_size.raw_x - 2.0;
+        if label_width > max_width {
+            font_size = (font_size as f64 * max_width / label_width) as u32;
+            label_width = glyphs.width(font_size, label).unwrap();
+        }
+
+        let transform = context.transform.trans(
+            cell_raw_x + (self.settings.cell_size.raw_x - label_width) / 2.0,
+            cell_raw_y + self.settings.cell_offset.raw_y,
+        );
+        text::Text::new_color(self.settings.theme.text, font_size)
+            .draw(label, glyphs, &context.draw_state, transform, g2d)
+            .unwrap();
+    }
+
+    //Overlay with the search statistics in the top left corner
+    fn render_hud(&self, context: Context, g2d: &mut G2d, glyphs: &mut Glyphs) {
+        let mut lines = vec![self.algorithm_type.name().to_string()];
+        {
+            let state = self.state.get();
+            let stats = &state.stats;
+            lines.push(format!("expanded: {}  frontier: {}", stats.expanded, stats.frontier));
+            match (stats.current_g, stats.current_f) {
+                (Some(g), Some(f)) => lines.push(format!("g: {}  f: {}", g, f)),
+                (None, Some(f)) => lines.push(format!("f: {}", f)),
+                _ => {}
+            }
+            lines.push(format!("time: {:.2} s", stats.elapsed().as_secs_f64()));
+            lines.push(match (stats.path_length, stats.path_cost) {
+                (Some(length), Some(cost)) => format!("path: {} cells, cost {}", length, cost),
+                _ if self.reachable_region.is_some() => "path: no path exists".to_string(),
+                _ if stats.is_finished() => "path: not found".to_string(),
+                _ => "path: searching".to_string(),
+            });
+        }
+        // only parameters the algorithm reads are shown
+        let info = self.algorithm_type.info();
+        lines.push(if info.parameters.contains(&Parameter::Heuristic) {
+            format!(
+                "heuristic: {}{}  neighbors: {}",
+                self.settings.heuristic.name(),
+                if self.settings.heuristic.is_admissible(self.settings.neighborhood) { "" } else { " (overestimates)" },
+                self.settings.neighborhood.name()
+            )
+        } else {
+            format!("neighbors: {}", self.settings.neighborhood.name())
+        });
+        lines.push(format!("labels: {}", self.settings.cell_label.name()));
+        lines.push(format!("theme: {}", self.settings.theme.kind.name()));
+        lines.push(match self.settings.scenario {
+            Some(index) => format!("map: {}", SCENARIOS[index].name),
+            None => format!("seed: {}", self.settings.seed),
+        });
+
+        let font_size = self.settings.font_size;
+        let line_height = font_size as f64 * 1.4;
+        let padding = self.settings.cell_offset.raw_x;
+        let hud_width = lines
+            .iter()
+            .map(|line| glyphs.width(font_size, line).unwrap())
+            .fold(0.0, f64::max);
+        rectangle(
+            self.settings.theme.hud_background,
+            [0.0, 0.0, hud_width + padding * 2.0, line_height * lines.len() as f64 + padding],
+            context.transform,
+            g2d,
+        );
+        for (n, line) in lines.iter().enumerate() {
+            text::Text::new_color(self.settings.theme.text, font_size)
+                .draw(
+                    line,
+                    glyphs,
+                    &context.draw_state,
+                    context.transform.trans(padding, line_height * (n + 1) as f64),
+                    g2d,
+                )
+                .unwrap();
+        }
+    }
+
+    fn render_reachable_region(&self, reachable_region: &HashSet<CellCoordinates>, context: Context, g2d: &mut G2d) {
+        for coordinates in reachable_region {
+            rectangle(
+                self.settings.theme.reachable_cell,
+                [
+                    coordinates.x as f64 * self.settings.cell_size.raw_x,
+                    coordinates.y as f64 * self.settings.cell_size.raw_y,
+                    self.settings.cell_size.raw_x,
+                    self.settings.cell_size.raw_y,
+                ],
+                context.transform,
+                g2d,
+            );
+        }
+    }
+
+    fn update_verdict(&mut self) {
+        if self.verdict.is_some() {
+            return;
+        }
+        let mut state = self.state.get();
+        let (true, Some(cost)) = (state.stats.is_finished(), state.stats.path_cost) else {
+            return;
+        };
+        let path = state.path.clone();
+        self.verdict = Some(PathVerdict::check(state.field(), &path, cost));
+    }
+
+    //Badge in the top right corner: is the path valid and how far it is from the optimal one
+    fn render_verdict(&self, verdict: &PathVerdict, context: Context, g2d: &mut G2d, glyphs: &mut Glyphs) {
+        let theme = self.settings.theme;
+        let message = verdict.describe();
+        let font_size = self.settings.font_size;
+        let padding = self.settings.cell_offset.raw_x;
+        let marker_size = font_size as f64;
+        let badge_width = glyphs.width(font_size, &message).unwrap() + marker_size + padding * 3.0;
+        let badge_height = font_size as f64 * 1.4 + padding;
+        let badge_x = self.settings.window_size.raw_x - badge_width;
+        rectangle(theme.hud_background, [badge_x, 0.0, badge_width, badge_height], context.transform, g2d);
+        let marker_color = match verdict {
+            PathVerdict::Optimal => theme.chosen_cell,
+            PathVerdict::Suboptimal(_) => theme.frontier_cell,
+            PathVerdict::Invalid(_) => theme.closed_cell,
+        };
+        rectangle(
+            marker_color,
+            [badge_x + padding, (badge_height - marker_size) / 2.0, marker_size, marker_size],
+            context.transform,
+            g2d,
+        );
+        text::Text::new_color(theme.text, font_size)
+            .draw(
+                &message,
+                glyphs,
+                &context.draw_state,
+                context.transform.trans(badge_x + marker_size + padding * 2.0, font_size as f64 * 1.4),
+                g2d,
+            )
+            .unwrap();
+    }
+
+    fn render_no_path_message(&self, context: Context, g2d: &mut G2d, glyphs: &mut Glyphs) {
+        let message = "No path exists";
+        let font_size = self.settings.font_size * 2;
+        let message_width = glyphs.width(font_size, message).unwrap();
+        let message_x = (self.settings.window_size.raw_x - message_width) / 2.0;
+        let message_y = self.settings.window_size.raw_y / 2.0;
+        rectangle(
+            self.settings.theme.message_background,
+            [
+                message_x - font_size as f64,
+                message_y - font_size as f64 * 1.5,
+                message_width + font_size as f64 * 2.0,
+                font_size as f64 * 2.5,
+            ],
+            context.transform,
+            g2d,
+        );
+        text::Text::new_color(self.settings.theme.text, font_size)
+            .draw(
+                message,
+                glyphs,
+                &context.draw_state,
+                context.transform.trans(message_x, message_y),
+                g2d,
+            )
+            .unwrap();
+    }
+
+    pub fn state(&self) -> &SharedState {
+        &self.state
+    }
+
+    pub fn set_cost_gradient(&mut self, cost_gradient: bool) {
+        self.settings.cost_gradient = cost_gradient;
+    }
+
+    pub fn set_cell_label(&mut self, cell_label: CellLabel) {
+        self.settings.cell_label = cell_label;
+    }
+
+    pub fn set_show_minimap(&mut self, show_minimap: bool) {
+        self.settings.show_minimap = show_minimap;
+    }
+
+    pub fn set_show_parents(&mut self, show_parents: bool) {
+        self.settings.show_parents = show_parents;
+    }
+
+    pub fn set_theme(&mut self, theme: Theme) {
+        self.settings.theme = theme;
+    }
+
+    pub fn set_show_hud(&mut self, show_hud: bool) {
+        self.settings.show_hud = show_hud;
+    }
+
+    pub fn set_window_size(&mut self, window_size: Vec2f) {
+        self.settings.window_size = window_size;
+    }
+
+    pub fn fit_to_window(&mut self) {
+        self.camera.fit(self.field_pixel_size(), self.settings.window_size);
+    }
+
+    pub fn zoom(&mut self, steps: f64) {
+        self.camera.zoom_at(self.mouse_coordinates, steps);
     }
 
     pub fn on_mouse_click(&mut self, button: &MouseButton) {
         if let &MouseButton::Left = button {
-            let x = (self.mouse_coordinates.raw_x / self.settings.cell_size.raw_x) as u16;
-            let y = (self.mouse_coordinates.raw_y / self.settings.cell_size.raw_y) as u16;
+            if self.jump_by_minimap() {
+                self.minimap_drag = true;
+                return;
+            }
+            self.drag_from = Some(self.mouse_coordinates);
+        }
+    }
 
-            println!(
-                "COLOR: {:?}", self.state.get().field().get_cell(x, y).get().get_state()
-            );
+    pub fn on_mouse_release(&mut self, button: &MouseButton) {
+        if let &MouseButton::Left = button {
+            self.drag_from = None;
+            self.minimap_drag = false;
         }
     }
 
     pub fn on_mouse_move(&mut self, args: &[f64; 2]) {
         self.mouse_coordinates.raw_x = args[0];
         self.mouse_coordinates.raw_y = args[1];
+        if let Some(drag_from) = self.drag_from {
+            self.camera.pan(args[0] - drag_from.raw_x, args[1] - drag_from.raw_y);
+            self.drag_from = Some(self.mouse_coordinates);
+        }
+        if self.minimap_drag {
+            self.jump_by_minimap();
+        }
     }
 }
@@ -0,0 +1,81 @@
+use piston_window::{Context, Transformed};
+
+use crate::settings::Vec2f;
+
+const MIN_ZOOM: f64 = 0.01;
+const MAX_ZOOM: f64 = 20.0;
+// zoom change for one step of the mouse wheel
+const ZOOM_STEP: f64 = 1.15;
+
+//Maps field pixels (cells of Settings::cell_size) to window pixels
+#[derive(Copy, Clone)]
+pub struct Camera {
+    // window position of the field origin
+    pub offset: Vec2f,
+    pub zoom: f64,
+}
+
+impl Camera {
+    //Camera showing the whole field
+    pub fn new(field_size: Vec2f, window_size: Vec2f) -> Camera {
+        let mut camera = Camera {
+            offset: Vec2f { raw_x: 0.0, raw_y: 0.0 },
+            zoom: 1.0,
+        };
+        camera.fit(field_size, window_size);
+        camera
+    }
+
+    pub fn apply(&self, context: Context) -> Context {
+        context
+            .trans(self.offset.raw_x, self.offset.raw_y)
+            .zoom(self.zoom)
+    }
+
+    //Window point to field point, for mouse picking
+    pub fn field_point(&self, point: Vec2f) -> Vec2f {
+        Vec2f {
+            raw_x: (point.raw_x - self.offset.raw_x) / self.zoom,
+            raw_y: (point.raw_y - self.offset.raw_y) / self.zoom,
+        }
+    }
+
+    //Zoom by wheel steps, the field point under the cursor stays in place
+    pub fn zoom_at(&mut self, point: Vec2f, steps: f64) {
+        let anchor = self.field_point(point);
+        self.zoom = (self.zoom * ZOOM_STEP.powf(steps)).clamp(MIN_ZOOM, MAX_ZOOM);
+        self.offset.raw_x = point.raw_x - anchor.raw_x * self.zoom;
+        self.offset.raw_y = point.raw_y - anchor.raw_y * self.zoom;
+    }
+
+    pub fn pan(&mut self, dx: f64, dy: f64) {
+        self.offset.raw_x += dx;
+        self.offset.raw_y += dy;
+    }
+
+    //Move the camera so the field point is in the middle of the window
+    pub fn center_on(&mut self, point: Vec2f, window_size: Vec2f) {
+        self.offset.raw_x = window_size.raw_x / 2.0 - point.raw_x * self.zoom;
+        self.offset.raw_y = window_size.raw_y / 2.0 - point.raw_y * self.zoom;
+    }
+
+    //Show the whole field centered in the window
+    pub fn fit(&mut self, field_size: Vec2f, window_size: Vec2f) {
+        self.zoom = (window_size.raw_x / field_size.raw_x)
+            .min(window_size.raw_y / field_size.raw_y)
+            .clamp(MIN_ZOOM, MAX_ZOOM);
+        self.offset.raw_x = (window_size.raw_x - field_size.raw_x * self.zoom) / 2.0;
+        self.offset.raw_y = (window_size.raw_y - field_size.raw_y * self.zoom) / 2.0;
+    }
+
+    //Window rectangle in field pixels: [x, y, width, height]
+    pub fn viewport(&self, window_size: Vec2f) -> [f64; 4] {
+        let origin = self.field_point(Vec2f { raw_x: 0.0, raw_y: 0.0 });
+        [
+            origin.raw_x,
+            origin.raw_y,
+            window_size.raw_x / self.zoom,
+            window_size.raw_y / self.zoom,
+        ]
+    }
+}
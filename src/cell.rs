@@ -16,7 +16,7 @@ impl Eq for Tile {}
 impl PartialEq for Tile {
     fn eq(&self, other: &Self) -> bool {
         // if arcs points to the same object
-        if Arc::ptr_eq(&self.deref(), &other.deref()) {
+        if Arc::ptr_eq(self.deref(), other.deref()) {
             return true;
         }
 
@@ -41,18 +41,21 @@ impl Clone for Tile {
 
 impl Hash for Tile {
     fn hash<H: Hasher>(&self, state: &mut H) {
-        let cell_guard = self.get();
-        cell_guard.state.hash(state);
-        cell_guard.coordinates.hash(state);
+        // only coordinates identify a cell, its state changes during a search
+        self.get().coordinates.hash(state);
     }
 }
 
-#[derive(Debug, Hash, Eq)]
+#[derive(Debug, Eq)]
 pub struct Cell {
     state: CellState,
     pub cost: i16,
     pub coordinates: CellCoordinates,
-    pub name: String,
+    // values from the last search: cost from start, heuristic, expansion index and parent
+    pub g: Option<i32>,
+    pub h: Option<i32>,
+    pub order: Option<usize>,
+    pub parent: Option<CellCoordinates>,
 }
 
 impl Cell {
@@ -61,10 +64,24 @@ impl Cell {
             state: CellState::Empty,
             cost: 10,
             coordinates: CellCoordinates { x, y },
-            name: format!("CELL_{}_{}", x, y),
+            g: None,
+            h: None,
+            order: None,
+            parent: None,
         }
     }
 
+    pub fn f(&self) -> Option<i32> {
+        Some(self.g? + self.h?)
+    }
+
+    pub fn clear_search_values(&mut self) {
+        self.g = None;
+        self.h = None;
+        self.order = None;
+        self.parent = None;
+    }
+
     pub fn get_state(&self) -> &CellState {
         &self.state
     }
@@ -81,24 +98,21 @@ impl PartialEq for Cell {
 }
 
 //TODO: another way to colorize start/end cells
-#[derive(Debug, Eq, Hash, PartialEq)]
+#[derive(Debug, Copy, Clone, Eq, Hash, PartialEq)]
 pub enum CellState {
-    Blocked, //obstacles -> Black?
-    Visited, //visited cells -> Red 0.5 alpha
-    Chosen,  //chosen path -> Green 0.5 alpha
+    Blocked,  //obstacles -> Black?
+    Frontier, //cells in the open list -> Orange 0.5 alpha
+    Closed,   //expanded cells -> Red 0.5 alpha
+    Current,  //cell being expanded -> Magenta
+    Reopened, //expanded cells pushed again with a better cost -> Purple 0.5 alpha
+    Chosen,   //chosen path -> Green 0.5 alpha
     Empty,   //empty cells -> Gray
     End,
     Start,
 }
 
-#[derive(Debug, Hash, Eq)]
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq)]
 pub struct CellCoordinates {
     pub x: u16,
     pub y: u16,
 }
-
-impl PartialEq for CellCoordinates {
-    fn eq(&self, other: &Self) -> bool {
-        (self.x == other.x) && (self.y == other.y)
-    }
-}
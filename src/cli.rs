@@ -0,0 +1,114 @@
+use std::path::PathBuf;
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+
+use crate::algorithms::{AlgorithmType, Heuristic};
+use crate::colors::ThemeKind;
+use crate::config::CONFIG_FILE;
+use crate::field::Neighborhood;
+
+//Command line options, every given option takes precedence over the config file
+#[derive(Parser)]
+#[command(name = "pathfinders", about = "Visualization of pathfinding algorithms")]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+    #[arg(long, global = true, default_value = CONFIG_FILE, help = "TOML config file, defaults are used when it is missing")]
+    pub config: PathBuf,
+    #[arg(long, global = true, help = "Number of columns of generated maps")]
+    pub columns: Option<u16>,
+    #[arg(long, global = true, help = "Number of rows of generated maps")]
+    pub rows: Option<u16>,
+    #[arg(long, global = true, help = "Cell size in pixels")]
+    pub cell_size: Option<u16>,
+    #[arg(long, global = true, help = "Share of blocked cells in [0; 1)")]
+    pub wall_density: Option<f64>,
+    #[arg(long, global = true, help = "Lowest cell cost")]
+    pub min_cost: Option<i16>,
+    #[arg(long, global = true, help = "Highest cell cost")]
+    pub max_cost: Option<i16>,
+    #[arg(long, global = true, help = "Map seed, random when not given")]
+    pub seed: Option<u64>,
+    #[arg(long, global = true, help = "Remove the fewest blocks needed to connect start and end")]
+    pub ensure_solvable: bool,
+    #[arg(long, global = true, value_enum, help = "Algorithm started right away, without the menu")]
+    pub algorithm: Option<AlgorithmType>,
+    #[arg(long, global = true, value_enum, help = "Heuristic of GBFS and A_STAR")]
+    pub heuristic: Option<Heuristic>,
+    #[arg(long, global = true, value_enum, help = "Neighbors of a cell: sides only or sides and corners")]
+    pub neighborhood: Option<Neighborhood>,
+    #[arg(long, global = true, help = "Animation speed multiplier")]
+    pub speed: Option<f64>,
+    #[arg(long, global = true, help = "Milliseconds between search steps at speed 1")]
+    pub step_delay: Option<f64>,
+    #[arg(long, global = true, value_enum, help = "Starting color theme")]
+    pub theme: Option<ThemeKind>,
+    #[arg(long, help = "Graph to search instead of a grid: DIMACS .gr or CSV edge list of from,to,cost")]
+    pub graph: Option<PathBuf>,
+    #[arg(long, requires = "graph", help = "Node coordinates of the graph: DIMACS .co or CSV of id,x,y")]
+    pub coordinates: Option<PathBuf>,
+    #[arg(long, requires = "graph", help = "Start node id of the graph, the first node when not given")]
+    pub from: Option<String>,
+    #[arg(long, requires = "graph", help = "Goal node id of the graph, the last node when not given")]
+    pub to: Option<String>,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    #[command(about = "Run one search without a window and print the result")]
+    Headless(HeadlessArgs),
+    #[command(about = "Run algorithms on many maps in parallel and write a CSV of the results")]
+    Experiment(ExperimentArgs),
+}
+
+#[derive(Args)]
+pub struct HeadlessArgs {
+    #[arg(long, help = "Map file in the format of assets/maps, a map is generated when not given")]
+    pub map: Option<PathBuf>,
+    #[arg(long, value_enum, default_value = "text", help = "Output format")]
+    pub format: OutputFormat,
+}
+
+#[derive(Copy, Clone, ValueEnum)]
+pub enum OutputFormat {
+    Text,
+    Json,
+}
+
+#[derive(Args)]
+pub struct ExperimentArgs {
+    #[arg(long, value_enum, value_delimiter = ',', help = "Algorithms to compare, all when not given")]
+    pub algorithms: Vec<AlgorithmType>,
+    #[arg(long, default_value_t = 10, help = "Maps for every combination of parameters, seeds follow the --seed one")]
+    pub maps: u64,
+    #[arg(long, value_delimiter = ',', value_parser = parse_size, help = "Map sizes, e.g. 30x20,50 (--columns and --rows when not given)")]
+    pub sizes: Vec<(u16, u16)>,
+    #[arg(long, value_delimiter = ',', help = "Wall densities, e.g. 0.1,0.3 (--wall-density when not given)")]
+    pub densities: Vec<f64>,
+    #[arg(long, value_delimiter = ',', value_parser = parse_cost_range, help = "Cost ranges, e.g. 1-1,0-19 (--min-cost and --max-cost when not given)")]
+    pub cost_ranges: Vec<(i16, i16)>,
+    #[arg(long = "map-file", help = "Map file used instead of generated maps, can be repeated")]
+    pub map_files: Vec<PathBuf>,
+    #[arg(long, help = "Worker threads, number of CPUs when not given")]
+    pub threads: Option<usize>,
+    #[arg(long, help = "CSV file, standard output when not given")]
+    pub output: Option<PathBuf>,
+}
+
+//"30x20" is 30 columns and 20 rows, "30" is a square
+fn parse_size(value: &str) -> Result<(u16, u16), String> {
+    let parse = |number: &str| number.trim().parse::<u16>().map_err(|error| format!("size '{}': {}", value, error));
+    match value.split_once('x') {
+        Some((columns, rows)) => Ok((parse(columns)?, parse(rows)?)),
+        None => parse(value).map(|side| (side, side)),
+    }
+}
+
+//"1-20" is costs from 1 to 20 inclusive
+fn parse_cost_range(value: &str) -> Result<(i16, i16), String> {
+    let (min, max) = value
+        .split_once('-')
+        .ok_or_else(|| format!("cost range '{}' must look like 1-20", value))?;
+    let parse = |number: &str| number.trim().parse::<i16>().map_err(|error| format!("cost range '{}': {}", value, error));
+    Ok((parse(min)?, parse(max)?))
+}
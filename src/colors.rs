@@ -1,9 +1,241 @@
-pub const EMPTY_FIELD_COLOR: [f32; 4] = [1.0, 1.0, 1.0, 1.0];
+use std::collections::HashMap;
 
-pub const EMPTY_CELL_COLOR: [f32; 4] = [0.95, 0.95, 0.95, 1.0];
-pub const CHOSEN_CELL_COLOR: [f32; 4] = [0.0, 1.0, 0.0, 1.0];
-pub const BLOCKED_CELL_COLOR: [f32; 4] = [0.0, 0.0, 0.0, 1.0];
-pub const VISITED_CELL_COLOR: [f32; 4] = [1.0, 0.0, 0.0, 0.5];
-pub const END_CELL_COLOR: [f32; 4] = [0.0, 0.0, 1.0, 1.0];
-pub const START_CELL_COLOR: [f32; 4] = [1.0, 0.0, 0.0, 1.0];
+use clap::ValueEnum;
+use piston_window::types::Color;
+use serde::Deserialize;
 
+#[derive(Copy, Clone, PartialEq, Deserialize, ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum ThemeKind {
+    Light,
+    Dark,
+    HighContrast,
+    ColorblindSafe,
+}
+
+impl ThemeKind {
+    pub fn next(self) -> ThemeKind {
+        match self {
+            ThemeKind::Light => ThemeKind::Dark,
+            ThemeKind::Dark => ThemeKind::HighContrast,
+            ThemeKind::HighContrast => ThemeKind::ColorblindSafe,
+            ThemeKind::ColorblindSafe => ThemeKind::Light,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            ThemeKind::Light => "light",
+            ThemeKind::Dark => "dark",
+            ThemeKind::HighContrast => "high contrast",
+            ThemeKind::ColorblindSafe => "colorblind safe",
+        }
+    }
+
+    pub fn theme(self) -> Theme {
+        match self {
+            ThemeKind::Light => LIGHT_THEME,
+            ThemeKind::Dark => DARK_THEME,
+            ThemeKind::HighContrast => HIGH_CONTRAST_THEME,
+            ThemeKind::ColorblindSafe => COLORBLIND_SAFE_THEME,
+        }
+    }
+}
+
+#[derive(Copy, Clone)]
+pub struct Theme {
+    pub kind: ThemeKind,
+    pub empty_field: Color,
+    pub empty_cell: Color,
+    pub chosen_cell: Color,
+    pub blocked_cell: Color,
+    pub frontier_cell: Color,
+    pub closed_cell: Color,
+    pub current_cell: Color,
+    pub reopened_cell: Color,
+    pub end_cell: Color,
+    pub start_cell: Color,
+    pub reachable_cell: Color,
+    pub grid_line: Color,
+    pub text: Color,
+    pub message_background: Color,
+    pub hud_background: Color,
+    pub parent_arrow: Color,
+    pub path_line: Color,
+    pub low_cost: Color,
+    pub high_cost: Color,
+    pub menu_background: Color,
+    pub menu_item: Color,
+    pub marked_menu_item: Color,
+    pub hovered_menu_item: Color,
+    // frame of the menu item chosen with the keyboard
+    pub focus_frame: Color,
+}
+
+pub const LIGHT_THEME: Theme = Theme {
+    kind: ThemeKind::Light,
+    empty_field: [1.0, 1.0, 1.0, 1.0],
+    empty_cell: [0.95, 0.95, 0.95, 1.0],
+    chosen_cell: [0.0, 1.0, 0.0, 1.0],
+    blocked_cell: [0.0, 0.0, 0.0, 1.0],
+    frontier_cell: [1.0, 0.6, 0.0, 0.5],
+    closed_cell: [1.0, 0.0, 0.0, 0.5],
+    current_cell: [0.8, 0.0, 0.8, 1.0],
+    reopened_cell: [0.5, 0.0, 1.0, 0.5],
+    end_cell: [0.0, 0.0, 1.0, 1.0],
+    start_cell: [1.0, 0.0, 0.0, 1.0],
+    reachable_cell: [1.0, 0.85, 0.0, 0.5],
+    grid_line: [0.0, 0.0, 0.0, 1.0],
+    text: [0.0, 0.0, 0.0, 1.0],
+    message_background: [1.0, 1.0, 1.0, 0.85],
+    hud_background: [1.0, 1.0, 1.0, 0.75],
+    parent_arrow: [0.1, 0.1, 0.4, 0.8],
+    path_line: [0.0, 0.35, 0.0, 1.0],
+    low_cost: [0.85, 0.95, 0.75, 1.0],
+    high_cost: [0.55, 0.35, 0.15, 1.0],
+    menu_background: [0.5, 0.5, 0.5, 1.0],
+    menu_item: [0.0, 1.0, 1.0, 1.0],
+    marked_menu_item: [1.0, 1.0, 0.0, 1.0],
+    hovered_menu_item: [0.6, 1.0, 1.0, 1.0],
+    focus_frame: [0.0, 0.0, 0.0, 1.0],
+};
+
+pub const DARK_THEME: Theme = Theme {
+    kind: ThemeKind::Dark,
+    empty_field: [0.1, 0.1, 0.12, 1.0],
+    empty_cell: [0.22, 0.22, 0.25, 1.0],
+    chosen_cell: [0.2, 0.75, 0.3, 1.0],
+    blocked_cell: [0.02, 0.02, 0.03, 1.0],
+    frontier_cell: [0.95, 0.6, 0.1, 0.5],
+    closed_cell: [0.85, 0.2, 0.2, 0.5],
+    current_cell: [0.9, 0.3, 0.9, 1.0],
+    reopened_cell: [0.6, 0.35, 1.0, 0.5],
+    end_cell: [0.3, 0.5, 1.0, 1.0],
+    start_cell: [1.0, 0.3, 0.3, 1.0],
+    reachable_cell: [0.95, 0.8, 0.1, 0.45],
+    grid_line: [0.08, 0.08, 0.1, 1.0],
+    text: [0.9, 0.9, 0.9, 1.0],
+    message_background: [0.1, 0.1, 0.12, 0.85],
+    hud_background: [0.1, 0.1, 0.12, 0.75],
+    parent_arrow: [0.75, 0.8, 1.0, 0.8],
+    path_line: [0.6, 1.0, 0.6, 1.0],
+    low_cost: [0.2, 0.3, 0.25, 1.0],
+    high_cost: [0.55, 0.4, 0.25, 1.0],
+    menu_background: [0.15, 0.15, 0.18, 1.0],
+    menu_item: [0.2, 0.45, 0.55, 1.0],
+    marked_menu_item: [0.6, 0.55, 0.15, 1.0],
+    hovered_menu_item: [0.3, 0.6, 0.72, 1.0],
+    focus_frame: [0.9, 0.9, 0.9, 1.0],
+};
+
+pub const HIGH_CONTRAST_THEME: Theme = Theme {
+    kind: ThemeKind::HighContrast,
+    empty_field: [1.0, 1.0, 1.0, 1.0],
+    empty_cell: [1.0, 1.0, 1.0, 1.0],
+    chosen_cell: [0.0, 0.9, 0.0, 1.0],
+    blocked_cell: [0.0, 0.0, 0.0, 1.0],
+    frontier_cell: [1.0, 1.0, 0.0, 1.0],
+    closed_cell: [0.0, 0.85, 1.0, 1.0],
+    current_cell: [1.0, 0.0, 1.0, 1.0],
+    reopened_cell: [1.0, 0.5, 0.0, 1.0],
+    end_cell: [0.0, 0.0, 1.0, 1.0],
+    start_cell: [1.0, 0.0, 0.0, 1.0],
+    reachable_cell: [1.0, 1.0, 0.0, 0.6],
+    grid_line: [0.0, 0.0, 0.0, 1.0],
+    text: [0.0, 0.0, 0.0, 1.0],
+    message_background: [1.0, 1.0, 1.0, 1.0],
+    hud_background: [1.0, 1.0, 1.0, 1.0],
+    parent_arrow: [0.0, 0.0, 0.0, 1.0],
+    path_line: [0.0, 0.0, 0.0, 1.0],
+    low_cost: [1.0, 1.0, 1.0, 1.0],
+    high_cost: [0.35, 0.35, 0.35, 1.0],
+    menu_background: [1.0, 1.0, 1.0, 1.0],
+    menu_item: [0.0, 1.0, 1.0, 1.0],
+    marked_menu_item: [1.0, 1.0, 0.0, 1.0],
+    hovered_menu_item: [0.7, 1.0, 1.0, 1.0],
+    focus_frame: [0.0, 0.0, 0.0, 1.0],
+};
+
+// Okabe-Ito palette, distinguishable with deuteranopia and protanopia
+pub const COLORBLIND_SAFE_THEME: Theme = Theme {
+    kind: ThemeKind::ColorblindSafe,
+    empty_field: [1.0, 1.0, 1.0, 1.0],
+    empty_cell: [0.95, 0.95, 0.95, 1.0],
+    chosen_cell: [0.94, 0.89, 0.26, 1.0],
+    blocked_cell: [0.0, 0.0, 0.0, 1.0],
+    frontier_cell: [0.9, 0.62, 0.0, 0.6],
+    closed_cell: [0.34, 0.71, 0.91, 0.6],
+    current_cell: [0.84, 0.37, 0.0, 1.0],
+    reopened_cell: [0.8, 0.47, 0.65, 0.6],
+    end_cell: [0.0, 0.45, 0.7, 1.0],
+    start_cell: [0.0, 0.62, 0.45, 1.0],
+    reachable_cell: [0.0, 0.62, 0.45, 0.5],
+    grid_line: [0.0, 0.0, 0.0, 1.0],
+    text: [0.0, 0.0, 0.0, 1.0],
+    message_background: [1.0, 1.0, 1.0, 0.85],
+    hud_background: [1.0, 1.0, 1.0, 0.75],
+    parent_arrow: [0.0, 0.45, 0.7, 0.8],
+    path_line: [0.0, 0.45, 0.7, 1.0],
+    low_cost: [0.95, 0.95, 0.95, 1.0],
+    high_cost: [0.0, 0.45, 0.7, 1.0],
+    menu_background: [0.6, 0.6, 0.6, 1.0],
+    menu_item: [0.34, 0.71, 0.91, 1.0],
+    marked_menu_item: [0.94, 0.89, 0.26, 1.0],
+    hovered_menu_item: [0.6, 0.84, 0.96, 1.0],
+    focus_frame: [0.0, 0.0, 0.0, 1.0],
+};
+
+impl Theme {
+    //Replace colors by their names, e.g. "blocked_cell"
+    pub fn with_overrides(mut self, colors: &HashMap<String, Color>) -> Result<Theme, String> {
+        for (name, color) in colors {
+            let target = match name.as_str() {
+                "empty_field" => &mut self.empty_field,
+                "empty_cell" => &mut self.empty_cell,
+                "chosen_cell" => &mut self.chosen_cell,
+                "blocked_cell" => &mut self.blocked_cell,
+                "frontier_cell" => &mut self.frontier_cell,
+                "closed_cell" => &mut self.closed_cell,
+                "current_cell" => &mut self.current_cell,
+                "reopened_cell" => &mut self.reopened_cell,
+                "end_cell" => &mut self.end_cell,
+                "start_cell" => &mut self.start_cell,
+                "reachable_cell" => &mut self.reachable_cell,
+                "grid_line" => &mut self.grid_line,
+                "text" => &mut self.text,
+                "message_background" => &mut self.message_background,
+                "hud_background" => &mut self.hud_background,
+                "parent_arrow" => &mut self.parent_arrow,
+                "path_line" => &mut self.path_line,
+                "low_cost" => &mut self.low_cost,
+                "high_cost" => &mut self.high_cost,
+                "menu_background" => &mut self.menu_background,
+                "menu_item" => &mut self.menu_item,
+                "marked_menu_item" => &mut self.marked_menu_item,
+                "hovered_menu_item" => &mut self.hovered_menu_item,
+                "focus_frame" => &mut self.focus_frame,
+                _ => return Err(format!("unknown theme color '{}'", name)),
+            };
+            if color.iter().any(|channel| !(0.0..=1.0).contains(channel)) {
+                return Err(format!("theme color '{}' channels must be in [0; 1]", name));
+            }
+            *target = *color;
+        }
+        Ok(self)
+    }
+
+    //Linear gradient between low and high cost colors over the cost range of the field
+    pub fn cost_gradient_color(&self, cost: i16, (min_cost, max_cost): (i16, i16)) -> Color {
+        // a field of one cost is drawn in the low cost color
+        let t = if max_cost > min_cost {
+            ((cost as f32 - min_cost as f32) / (max_cost as f32 - min_cost as f32)).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        let mut color = [0.0; 4];
+        for (i, channel) in color.iter_mut().enumerate() {
+            *channel = self.low_cost[i] + (self.high_cost[i] - self.low_cost[i]) * t;
+        }
+        color
+    }
+}
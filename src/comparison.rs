@@ -0,0 +1,107 @@
+use piston_window::{clear, rectangle, text, Context, G2d, Glyphs, Transformed};
+
+use crate::algorithms::AlgorithmType;
+use crate::app::App;
+use crate::colors::Theme;
+use crate::field::Field;
+use crate::run::SearchRun;
+use crate::settings::{Settings, Vec2f};
+
+const HEADER_HEIGHT: f64 = 30.0;
+
+//Several algorithms searching copies of the same field at the same time
+pub struct Comparison {
+    panels: Vec<(AlgorithmType, App)>,
+    settings: Settings,
+}
+
+impl Comparison {
+    pub fn new(settings: Settings, algorithm_types: &[AlgorithmType]) -> Comparison {
+        Comparison::with_field(settings, algorithm_types, Field::generate(&settings))
+    }
+
+    //Every panel searches its own copy of the field
+    pub fn with_field(settings: Settings, algorithm_types: &[AlgorithmType], field: Field) -> Comparison {
+        let panels = algorithm_types
+            .iter()
+            .map(|algorithm_type| {
+                let mut app = App::with_field(settings, algorithm_type, field.duplicate());
+                app.start();
+                (*algorithm_type, app)
+            })
+            .collect();
+        Comparison { panels, settings }
+    }
+
+    //Search threads of all panels, taken to be stopped
+    pub fn take_runs(&mut self) -> Vec<SearchRun> {
+        self.panels.iter_mut().filter_map(|(_, app)| app.take_run()).collect()
+    }
+
+    pub fn set_theme(&mut self, theme: Theme) {
+        self.settings.theme = theme;
+        for (_, app) in self.panels.iter_mut() {
+            app.set_theme(theme);
+        }
+    }
+
+    pub fn set_window_size(&mut self, window_size: Vec2f) {
+        self.settings.window_size = window_size;
+        for (_, app) in self.panels.iter_mut() {
+            app.set_window_size(window_size);
+            app.fit_to_window();
+        }
+    }
+
+    pub fn render(&mut self, context: Context, g2d: &mut G2d, glyphs: &mut Glyphs) {
+        clear(self.settings.theme.empty_field, g2d);
+
+        let columns = (self.panels.len() as f64).sqrt().ceil() as usize;
+        let rows = self.panels.len().div_ceil(columns);
+        let panel_width = self.settings.window_size.raw_x / columns as f64;
+        let panel_height = self.settings.window_size.raw_y / rows as f64;
+        let field_size = match self.panels.first() {
+            Some((_, app)) => app.field_pixel_size(),
+            None => return,
+        };
+        let scale = (panel_width / field_size.raw_x)
+            .min((panel_height - HEADER_HEIGHT) / field_size.raw_y);
+
+        for (n, (algorithm_type, app)) in self.panels.iter_mut().enumerate() {
+            let panel_x = (n % columns) as f64 * panel_width;
+            let panel_y = (n / columns) as f64 * panel_height;
+
+            let header = {
+                let state = app.state().get();
+                let path_cost = match state.stats.path_cost {
+                    Some(cost) => cost.to_string(),
+                    None => "-".to_string(),
+                };
+                format!(
+                    "{}  expanded: {}  cost: {}",
+                    algorithm_type.name(),
+                    state.stats.expanded,
+                    path_cost
+                )
+            };
+            text::Text::new_color(self.settings.theme.text, (HEADER_HEIGHT * 0.6) as u32)
+                .draw(
+                    &header,
+                    glyphs,
+                    &context.draw_state,
+                    context.transform.trans(panel_x + 5.0, panel_y + HEADER_HEIGHT * 0.75),
+                    g2d,
+                )
+                .unwrap();
+
+            let panel_context = context
+                .trans(panel_x, panel_y + HEADER_HEIGHT)
+                .scale(scale, scale);
+            app.render_field(panel_context, g2d, glyphs);
+
+            //panel separators
+            rectangle(self.settings.theme.grid_line, [panel_x, panel_y, 2.0, panel_height], context.transform, g2d);
+            rectangle(self.settings.theme.grid_line, [panel_x, panel_y, panel_width, 2.0], context.transform, g2d);
+        }
+    }
+}
@@ -0,0 +1,217 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::Path;
+
+use piston_window::types::Color;
+use serde::Deserialize;
+
+use crate::algorithms::{AlgorithmType, Heuristic};
+use crate::cli::Cli;
+use crate::colors::ThemeKind;
+use crate::field::Neighborhood;
+use crate::settings::Settings;
+
+pub const CONFIG_FILE: &str = "pathfinders.toml";
+
+// coordinates are stepped as i16, so bigger fields overflow
+pub const MAX_CELLS_NUMBER: u16 = 1000;
+// path costs are summed as i32, this keeps the longest path on the biggest field below its limit
+pub const MAX_CELL_COST: i16 = 1000;
+const MAX_CELL_SIZE: u16 = 200;
+
+#[derive(Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    #[serde(default)]
+    pub grid: GridConfig,
+    #[serde(default)]
+    pub search: SearchConfig,
+    #[serde(default)]
+    pub window: WindowConfig,
+    #[serde(default)]
+    pub theme: ThemeConfig,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GridConfig {
+    pub columns: Option<u16>,
+    pub rows: Option<u16>,
+    pub cell_size: Option<u16>,
+    pub wall_density: Option<f64>,
+    pub min_cost: Option<i16>,
+    pub max_cost: Option<i16>,
+    pub seed: Option<u64>,
+    pub ensure_solvable: Option<bool>,
+    // costs from coherent noise instead of independent random ones
+    pub terrain: Option<bool>,
+    pub neighborhood: Option<Neighborhood>,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SearchConfig {
+    pub algorithm: Option<AlgorithmType>,
+    pub heuristic: Option<Heuristic>,
+    pub speed: Option<f64>,
+    pub step_delay: Option<f64>,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WindowConfig {
+    pub menu_font_size: Option<u32>,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ThemeConfig {
+    pub base: Option<ThemeKind>,
+    // colors replaced in every built-in theme
+    #[serde(default)]
+    pub colors: HashMap<String, Color>,
+}
+
+impl Config {
+    //Missing config file is not an error, defaults are used then
+    pub fn load(path: &Path) -> Result<Config, String> {
+        match fs::read_to_string(path) {
+            Ok(content) => toml::from_str(&content).map_err(|error| format!("{}: {}", path.display(), error)),
+            Err(error) if error.kind() == ErrorKind::NotFound => Ok(Config::default()),
+            Err(error) => Err(format!("{}: {}", path.display(), error)),
+        }
+    }
+
+    //Command line options replace the values of the file
+    pub fn merge(&mut self, cli: &Cli) {
+        let grid = &mut self.grid;
+        grid.columns = cli.columns.or(grid.columns);
+        grid.rows = cli.rows.or(grid.rows);
+        grid.cell_size = cli.cell_size.or(grid.cell_size);
+        grid.wall_density = cli.wall_density.or(grid.wall_density);
+        grid.min_cost = cli.min_cost.or(grid.min_cost);
+        grid.max_cost = cli.max_cost.or(grid.max_cost);
+        grid.seed = cli.seed.or(grid.seed);
+        if cli.ensure_solvable {
+            grid.ensure_solvable = Some(true);
+        }
+        grid.neighborhood = cli.neighborhood.or(grid.neighborhood);
+
+        let search = &mut self.search;
+        search.algorithm = cli.algorithm.or(search.algorithm);
+        search.heuristic = cli.heuristic.or(search.heuristic);
+        search.speed = cli.speed.or(search.speed);
+        search.step_delay = cli.step_delay.or(search.step_delay);
+
+        self.theme.base = cli.theme.or(self.theme.base);
+    }
+
+    //Validated settings, missing values are the defaults
+    pub fn settings(&self) -> Result<Settings, String> {
+        let grid = &self.grid;
+        let columns = grid.columns.unwrap_or(30);
+        let rows = grid.rows.unwrap_or(30);
+        let cell_size = grid.cell_size.unwrap_or(30);
+        if !(1..=MAX_CELL_SIZE).contains(&cell_size) {
+            return Err(format!("cell size must be in [1; {}]", MAX_CELL_SIZE));
+        }
+
+        let mut settings = Settings::new(cell_size, columns, rows);
+        settings.seed = grid.seed.unwrap_or_else(rand::random);
+        settings.ensure_solvable = grid.ensure_solvable.unwrap_or(settings.ensure_solvable);
+        settings.terrain = grid.terrain.unwrap_or(settings.terrain);
+        settings.neighborhood = grid.neighborhood.unwrap_or(settings.neighborhood);
+
+        settings.wall_density = grid.wall_density.unwrap_or(settings.wall_density);
+        settings.min_cost = grid.min_cost.unwrap_or(settings.min_cost);
+        settings.max_cost = grid.max_cost.unwrap_or(settings.max_cost.max(settings.min_cost));
+        check_map_parameters(&settings)?;
+
+        let search = &self.search;
+        // the default heuristic has to stay admissible with diagonal steps
+        let default_heuristic = match settings.neighborhood {
+            Neighborhood::Four => settings.heuristic,
+            Neighborhood::Eight => Heuristic::Chebyshev,
+        };
+        settings.heuristic = search.heuristic.unwrap_or(default_heuristic);
+        settings.speed = search.speed.unwrap_or(settings.speed);
+        if !settings.speed.is_finite() || settings.speed <= 0.0 {
+            return Err("speed must be a positive number".to_string());
+        }
+        settings.step_delay = search.step_delay.unwrap_or(settings.step_delay);
+        if !settings.step_delay.is_finite() || settings.step_delay < 0.0 {
+            return Err("step delay must be a non-negative number".to_string());
+        }
+
+        settings.menu_font_size = self.window.menu_font_size.unwrap_or(settings.menu_font_size);
+        if settings.menu_font_size == 0 {
+            return Err("menu font size must be positive".to_string());
+        }
+
+        settings.theme = self.theme.base
+            .unwrap_or(ThemeKind::Light)
+            .theme()
+            .with_overrides(&self.theme.colors)?;
+        Ok(settings)
+    }
+}
+
+//Warning about a heuristic that makes A_STAR paths not optimal
+pub fn heuristic_warning(settings: &Settings) -> Option<String> {
+    (!settings.heuristic.is_admissible(settings.neighborhood)).then(|| {
+        format!(
+            "heuristic {} overestimates with {} neighbors, A_STAR paths may not be optimal (use chebyshev)",
+            settings.heuristic.name(),
+            settings.neighborhood.name()
+        )
+    })
+}
+
+//Write values into sections of the config file by section and key. Only these values
+//change, the rest of the file is kept as it is, with its comments and order
+pub fn save_values(path: &Path, values: Vec<(&str, &str, toml_edit::Value)>) -> Result<(), String> {
+    let with_path = |error: String| format!("{}: {}", path.display(), error);
+    let mut document: toml_edit::DocumentMut = match fs::read_to_string(path) {
+        Ok(content) => content.parse().map_err(|error: toml_edit::TomlError| with_path(error.to_string()))?,
+        Err(error) if error.kind() == ErrorKind::NotFound => toml_edit::DocumentMut::new(),
+        Err(error) => return Err(with_path(error.to_string())),
+    };
+    for (section, key, value) in values {
+        let section_table = document
+            .entry(section)
+            .or_insert_with(toml_edit::table)
+            .as_table_like_mut()
+            .ok_or_else(|| with_path(format!("`{}` is not a section", section)))?;
+        match section_table.get_mut(key).and_then(|item| item.as_value_mut()) {
+            // the comment after the old value stays
+            Some(old_value) => {
+                let decor = old_value.decor().clone();
+                *old_value = value;
+                *old_value.decor_mut() = decor;
+            }
+            None => {
+                section_table.insert(key, toml_edit::value(value));
+            }
+        }
+    }
+    fs::write(path, document.to_string()).map_err(|error| with_path(error.to_string()))
+}
+
+//Checks values used to generate a map
+pub fn check_map_parameters(settings: &Settings) -> Result<(), String> {
+    let cells_numbers = 2..=MAX_CELLS_NUMBER;
+    if !cells_numbers.contains(&settings.columns) || !cells_numbers.contains(&settings.rows) {
+        return Err(format!("columns and rows must be in [2; {}]", MAX_CELLS_NUMBER));
+    }
+    if !(0.0..1.0).contains(&settings.wall_density) {
+        return Err("wall density must be in [0; 1)".to_string());
+    }
+    if settings.min_cost < 0 || settings.min_cost > settings.max_cost || settings.max_cost > MAX_CELL_COST {
+        return Err(format!(
+            "cost range [{}; {}] is invalid, costs must be in [0; {}] and min cost not above max cost",
+            settings.min_cost, settings.max_cost, MAX_CELL_COST
+        ));
+    }
+    Ok(())
+}
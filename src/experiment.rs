@@ -0,0 +1,230 @@
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::PathBuf;
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use crate::algorithms::registry::ALGORITHMS;
+use crate::algorithms::AlgorithmType;
+use crate::cli::ExperimentArgs;
+use crate::config::check_map_parameters;
+use crate::field::Field;
+use crate::headless::{load_map, search};
+use crate::settings::Settings;
+use crate::validation::{optimal_cost, validate_path};
+
+const CSV_HEADER: &str = "map,columns,rows,wall_density,min_cost,max_cost,seed,algorithm,found,valid,\
+path_length,path_cost,optimal_cost,optimality_gap,expanded,runtime_ms";
+
+//A map of the experiment, every selected algorithm searches it
+enum MapSource {
+    Generated(Settings),
+    File(PathBuf, Settings),
+}
+
+struct Run {
+    algorithm: AlgorithmType,
+    path_length: Option<usize>,
+    path_cost: Option<i32>,
+    // path passed validate_path
+    valid: bool,
+    expanded: usize,
+    runtime_ms: f64,
+}
+
+struct MapResult {
+    map: String,
+    columns: u16,
+    rows: u16,
+    settings: Settings,
+    // density, costs and seed are known only for generated maps
+    generated: bool,
+    // reference for the optimality gap
+    optimal_cost: Option<i32>,
+    runs: Vec<Run>,
+}
+
+pub fn run(settings: Settings, args: &ExperimentArgs) -> Result<(), String> {
+    let algorithms = if args.algorithms.is_empty() {
+        ALGORITHMS.iter().map(|info| info.algorithm_type).collect()
+    } else {
+        args.algorithms.clone()
+    };
+    let maps = map_sources(settings, args)?;
+    let threads = args
+        .threads
+        .unwrap_or_else(|| thread::available_parallelism().map_or(1, |threads| threads.get()))
+        .clamp(1, maps.len().max(1));
+
+    let jobs = Arc::new(Mutex::new(maps.into_iter().enumerate().collect::<Vec<_>>()));
+    let (sender, receiver) = mpsc::channel();
+    let workers: Vec<_> = (0..threads)
+        .map(|n| {
+            let jobs = Arc::clone(&jobs);
+            let sender = sender.clone();
+            let algorithms = algorithms.clone();
+            thread::Builder::new()
+                .name(format!("experiment-{}", n))
+                .spawn(move || loop {
+                    let Some((index, source)) = jobs.lock().unwrap().pop() else {
+                        break;
+                    };
+                    let result = run_map(source, &algorithms);
+                    if sender.send((index, result)).is_err() {
+                        break;
+                    }
+                })
+                .unwrap()
+        })
+        .collect();
+    drop(sender);
+
+    let mut results: Vec<(usize, Result<MapResult, String>)> = receiver.iter().collect();
+    for worker in workers {
+        worker.join().map_err(|_| "experiment worker panicked".to_string())?;
+    }
+    // workers finish in any order, the CSV follows the order of maps
+    results.sort_by_key(|(index, _)| *index);
+    let results = results
+        .into_iter()
+        .map(|(_, result)| result)
+        .collect::<Result<Vec<MapResult>, String>>()?;
+
+    let runs: usize = results.iter().map(|result| result.runs.len()).sum();
+    match &args.output {
+        Some(path) => {
+            let file = File::create(path).map_err(|error| format!("{}: {}", path.display(), error))?;
+            write_csv(&results, BufWriter::new(file)).map_err(|error| format!("{}: {}", path.display(), error))?;
+            eprintln!("{} runs on {} maps written to {}", runs, results.len(), path.display());
+        }
+        None => write_csv(&results, io::stdout().lock()).map_err(|error| error.to_string())?,
+    }
+    Ok(())
+}
+
+//Every combination of sizes, densities and cost ranges, each with its own seeds
+fn map_sources(settings: Settings, args: &ExperimentArgs) -> Result<Vec<MapSource>, String> {
+    if !args.map_files.is_empty() {
+        return Ok(args.map_files.iter().map(|path| MapSource::File(path.clone(), settings)).collect());
+    }
+
+    let sizes = if args.sizes.is_empty() { vec![(settings.columns, settings.rows)] } else { args.sizes.clone() };
+    let densities = if args.densities.is_empty() { vec![settings.wall_density] } else { args.densities.clone() };
+    let cost_ranges = if args.cost_ranges.is_empty() {
+        vec![(settings.min_cost, settings.max_cost)]
+    } else {
+        args.cost_ranges.clone()
+    };
+
+    let mut maps = Vec::new();
+    for &(columns, rows) in sizes.iter() {
+        for &wall_density in densities.iter() {
+            for &(min_cost, max_cost) in cost_ranges.iter() {
+                let mut map_settings = settings;
+                map_settings.columns = columns;
+                map_settings.rows = rows;
+                map_settings.wall_density = wall_density;
+                map_settings.min_cost = min_cost;
+                map_settings.max_cost = max_cost;
+                map_settings.scenario = None;
+                check_map_parameters(&map_settings)?;
+                for n in 0..args.maps {
+                    map_settings.seed = settings.seed.wrapping_add(n);
+                    maps.push(MapSource::Generated(map_settings));
+                }
+            }
+        }
+    }
+    Ok(maps)
+}
+
+fn run_map(source: MapSource, algorithms: &[AlgorithmType]) -> Result<MapResult, String> {
+    let generated = matches!(source, MapSource::Generated(_));
+    let (map, settings, field) = match source {
+        MapSource::Generated(settings) => ("generated".to_string(), settings, Field::generate(&settings)),
+        MapSource::File(path, settings) => {
+            let field = load_map(&path, &settings)?;
+            (path.display().to_string(), settings, field)
+        }
+    };
+
+    let optimal_cost = optimal_cost(&field);
+    let runs = algorithms
+        .iter()
+        .map(|&algorithm| {
+            let state = search(&settings, algorithm, field.duplicate());
+            let mut state = state.get();
+            let path = state.path.clone();
+            let valid = state.stats.path_cost
+                .is_some_and(|cost| validate_path(state.field(), &path, cost).is_ok());
+            Run {
+                algorithm,
+                path_length: state.stats.path_length,
+                path_cost: state.stats.path_cost,
+                valid,
+                expanded: state.stats.expanded,
+                runtime_ms: state.stats.elapsed().as_secs_f64() * 1000.0,
+            }
+        })
+        .collect();
+
+    Ok(MapResult {
+        map,
+        columns: field.columns(),
+        rows: field.rows(),
+        settings,
+        generated,
+        optimal_cost,
+        runs,
+    })
+}
+
+fn write_csv(results: &[MapResult], mut out: impl Write) -> io::Result<()> {
+    writeln!(out, "{}", CSV_HEADER)?;
+    let optional = |value: Option<String>| value.unwrap_or_default();
+    for result in results {
+        let settings = &result.settings;
+        let parameters = if result.generated {
+            format!("{},{},{},{}", settings.wall_density, settings.min_cost, settings.max_cost, settings.seed)
+        } else {
+            ",,,".to_string()
+        };
+        for run in result.runs.iter() {
+            // relative excess over the optimal cost, 0 is an optimal path. It is not defined
+            // for a worse path when the optimum is free, the field is empty then
+            let gap = match (run.path_cost, result.optimal_cost) {
+                (Some(cost), Some(optimal)) if optimal > 0 => Some((cost - optimal) as f64 / optimal as f64),
+                (Some(cost), Some(optimal)) if cost == optimal => Some(0.0),
+                _ => None,
+            };
+            writeln!(
+                out,
+                "{},{},{},{},{},{},{},{},{},{},{},{},{:.3}",
+                csv_field(&result.map),
+                result.columns,
+                result.rows,
+                parameters,
+                run.algorithm.name(),
+                run.path_cost.is_some(),
+                run.valid,
+                optional(run.path_length.map(|length| length.to_string())),
+                optional(run.path_cost.map(|cost| cost.to_string())),
+                optional(result.optimal_cost.map(|cost| cost.to_string())),
+                optional(gap.map(|gap| format!("{:.4}", gap))),
+                run.expanded,
+                run.runtime_ms,
+            )?;
+        }
+    }
+    out.flush()
+}
+
+//Quote values with separators, file names may have them
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
@@ -1,81 +1,309 @@
+use std::collections::{HashSet, VecDeque};
 use std::sync::{Arc, Mutex};
 
-use rand::Rng;
+use clap::ValueEnum;
+use noise::{Fbm, MultiFractal, NoiseFn, Perlin};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::Deserialize;
 
-use crate::cell::{Cell, CellState, Tile};
+use crate::cell::{Cell, CellCoordinates, CellState, Tile};
+use crate::scenario::SCENARIOS;
+use crate::settings::Settings;
+
+//Which cells are neighbors: sides only, or sides and corners
+#[derive(Copy, Clone, PartialEq, Deserialize, ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum Neighborhood {
+    Four,
+    Eight,
+}
+
+impl Neighborhood {
+    pub fn name(self) -> &'static str {
+        match self {
+            Neighborhood::Four => "4",
+            Neighborhood::Eight => "8",
+        }
+    }
+}
 
 pub struct Field {
     pub cells: Vec<Vec<Tile>>,
+    pub start: CellCoordinates,
+    pub end: CellCoordinates,
+    pub neighborhood: Neighborhood,
 }
 
 impl Field {
-    pub fn new(cells_number: u16) -> Field {
+    pub fn new(columns: u16, rows: u16) -> Field {
         Field {
-            cells: (0..cells_number)
+            cells: (0..columns)
                 .map(|x| {
-                    (0..cells_number)
+                    (0..rows)
                         .map(|y| Tile(Arc::new(Mutex::new(Cell::new(x, y)))))
                         .collect()
                 })
                 .collect::<Vec<Vec<Tile>>>(),
+            start: CellCoordinates { x: 0, y: 0 },
+            end: CellCoordinates {
+                x: columns - 1,
+                y: rows - 1,
+            },
+            neighborhood: Neighborhood::Four,
+        }
+    }
+
+    pub fn columns(&self) -> u16 {
+        self.cells.len() as u16
+    }
+
+    pub fn rows(&self) -> u16 {
+        self.cells.first().map_or(0, |column| column.len()) as u16
+    }
+
+    //Same settings and seed always give the same field
+    pub fn generate(settings: &Settings) -> Field {
+        if let Some(index) = settings.scenario {
+            let mut field = SCENARIOS[index].build();
+            field.neighborhood = settings.neighborhood;
+            return field;
+        }
+
+        let mut field = Field::new(settings.columns, settings.rows);
+        field.neighborhood = settings.neighborhood;
+        // walls, prices and terrain draw from their own seeds, so they do not follow the same random sequence
+        let [walls_seed, prices_seed, terrain_seed]: [u64; 3] = StdRng::seed_from_u64(settings.seed).random();
+        field.make_noise(walls_seed, settings.wall_density);
+        if settings.terrain {
+            field.make_terrain(terrain_seed, settings.min_cost, settings.max_cost);
+        } else {
+            field.set_prices(prices_seed, settings.min_cost, settings.max_cost);
+        }
+        field.mark_endpoints();
+        if settings.ensure_solvable {
+            field.repair();
+        }
+        field
+    }
+
+    //Deep copy, the copy shares no cells with the original
+    pub fn duplicate(&self) -> Field {
+        let mut field = Field::new(self.columns(), self.rows());
+        for (column, original_column) in field.cells.iter().zip(self.cells.iter()) {
+            for (tile, original_tile) in column.iter().zip(original_column.iter()) {
+                let original = original_tile.get();
+                let mut cell = tile.get();
+                cell.cost = original.cost;
+                cell.set_state(*original.get_state());
+            }
         }
+        field.start = self.start;
+        field.end = self.end;
+        field.neighborhood = self.neighborhood;
+        field
     }
+
+    //Remove search overlay, blocks and costs stay as they are
+    pub fn reset_search(&mut self) {
+        for column in self.cells.iter() {
+            for tile in column.iter() {
+                let mut cell = tile.get();
+                if !matches!(cell.get_state(), CellState::Blocked | CellState::Empty) {
+                    cell.set_state(CellState::Empty);
+                }
+                cell.clear_search_values();
+            }
+        }
+        self.mark_endpoints();
+    }
+
+    pub fn start_cell(&self) -> Tile {
+        self.get_cell(self.start.x, self.start.y)
+    }
+
+    pub fn end_cell(&self) -> Tile {
+        self.get_cell(self.end.x, self.end.y)
+    }
+
+    pub fn mark_endpoints(&mut self) {
+        self.start_cell().get().set_state(CellState::Start);
+        self.end_cell().get().set_state(CellState::End);
+    }
+
     pub fn get_cell(&self, x: u16, y: u16) -> Tile {
         self.cells[x as usize][y as usize].clone()
     }
 
     //Check position by bounds
     fn is_valid_coordinates(&self, target_x: i16, target_y: i16) -> bool {
-        let acceptable = 0..self.cells.len();
-        acceptable.contains(&(target_x as usize)) && acceptable.contains(&(target_y as usize))
+        (0..self.columns() as i16).contains(&target_x) && (0..self.rows() as i16).contains(&target_y)
     }
 
     //Valid cell to path is cell with Empty type
     fn is_valid_to_path(&self, target_x: i16, target_y: i16) -> bool {
-        return match self
-            .get_cell(target_x as u16, target_y as u16)
-            .lock()
-            .unwrap()
-            .get_state()
-        {
-            CellState::Blocked => false,
-            _ => true,
-        };
+        !matches!(
+            self.get_cell(target_x as u16, target_y as u16).get().get_state(),
+            CellState::Blocked
+        )
     }
 
-    //Create blocks on a field
-    pub fn make_noise(&mut self) {
-        let mut rng = rand::thread_rng();
-        for _ in 0..(self.cells.len().pow(2) as f64 * 0.25).abs() as usize {
-            let pos_x = rng.gen_range(0..self.cells.len() as u16);
-            let pos_y = rng.gen_range(0..self.cells.len() as u16);
+    //All cells connected to the given one by not blocked cells
+    pub fn reachable_from(&self, from: CellCoordinates) -> HashSet<CellCoordinates> {
+        let mut reachable = HashSet::from([from]);
+        let mut queue = VecDeque::from([from]);
+        while let Some(current) = queue.pop_front() {
+            for (x, y) in self.passable_neighbors(current) {
+                let coordinates = CellCoordinates { x, y };
+                if reachable.insert(coordinates) {
+                    queue.push_back(coordinates);
+                }
+            }
+        }
+        reachable
+    }
+
+    pub fn is_solvable(&self) -> bool {
+        self.reachable_from(self.start).contains(&self.end)
+    }
+
+    //Connect start and end by removing the fewest blocks (0-1 BFS, a block costs 1).
+    //The opened path goes by sides only, so it suits both neighborhoods
+    pub fn repair(&mut self) {
+        let rows = self.rows() as usize;
+        let size = self.columns() as usize * rows;
+        let index = |c: CellCoordinates| c.x as usize * rows + c.y as usize;
+        let mut removed_blocks = vec![usize::MAX; size];
+        let mut parents: Vec<Option<CellCoordinates>> = vec![None; size];
+        let mut queue = VecDeque::from([self.start]);
+        removed_blocks[index(self.start)] = self.is_blocked(self.start) as usize;
+
+        while let Some(current) = queue.pop_front() {
+            if current == self.end {
+                break;
+            }
+            for (x, y) in self.neighbor_coordinates(current, Neighborhood::Four) {
+                let neighbor = CellCoordinates { x, y };
+                let weight = self.is_blocked(neighbor) as usize;
+                let distance = removed_blocks[index(current)] + weight;
+                if distance < removed_blocks[index(neighbor)] {
+                    removed_blocks[index(neighbor)] = distance;
+                    parents[index(neighbor)] = Some(current);
+                    if weight == 0 {
+                        queue.push_front(neighbor);
+                    } else {
+                        queue.push_back(neighbor);
+                    }
+                }
+            }
+        }
+
+        let mut cell = Some(self.end);
+        while let Some(coordinates) = cell {
+            if self.is_blocked(coordinates) {
+                self.get_cell(coordinates.x, coordinates.y)
+                    .get()
+                    .set_state(CellState::Empty);
+            }
+            cell = parents[index(coordinates)];
+        }
+    }
+
+    pub fn is_blocked(&self, coordinates: CellCoordinates) -> bool {
+        !self.is_valid_to_path(coordinates.x as i16, coordinates.y as i16)
+    }
+
+    //Create blocks on a field, density is a share of cells to block
+    pub fn make_noise(&mut self, seed: u64, density: f64) {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let size = self.columns() as usize * self.rows() as usize;
+        for _ in 0..(size as f64 * density).abs() as usize {
+            let pos_x = rng.random_range(0..self.columns());
+            let pos_y = rng.random_range(0..self.rows());
             self.get_cell(pos_x, pos_y)
                 .get()
                 .set_state(CellState::Blocked);
         }
     }
 
-    pub fn set_prices(&mut self) {
-        let mut rng = rand::thread_rng();
-        for x in 0..self.cells.len() - 1 {
-            for y in 0..self.cells.len() - 1 {
-                let tile = self.get_cell(x as u16, y as u16);
+    pub fn set_prices(&mut self, seed: u64, min_cost: i16, max_cost: i16) {
+        let mut rng = StdRng::seed_from_u64(seed);
+        for x in 0..self.columns() {
+            for y in 0..self.rows() {
+                let tile = self.get_cell(x, y);
+                if *tile.get().get_state() != CellState::Blocked {
+                    tile.get().cost = rng.random_range(min_cost..=max_cost);
+                }
+            }
+        }
+    }
+
+    //Set prices from coherent noise, so costs form hills and valleys
+    pub fn make_terrain(&mut self, seed: u64, min_cost: i16, max_cost: i16) {
+        let terrain = Fbm::<Perlin>::new(StdRng::seed_from_u64(seed).random())
+            .set_octaves(4)
+            .set_frequency(0.08);
+        let levels = (max_cost as f64) - (min_cost as f64) + 1.0;
+        for x in 0..self.columns() {
+            for y in 0..self.rows() {
+                let tile = self.get_cell(x, y);
                 if *tile.get().get_state() != CellState::Blocked {
-                    tile.get().cost = rng.gen_range(0..20);
+                    // fbm output is roughly in [-1; 1]
+                    let height = (terrain.get([x as f64, y as f64]) + 1.0) / 2.0;
+                    let cost = min_cost as f64 + (height * levels).floor();
+                    tile.get().cost = cost.clamp(min_cost as f64, max_cost as f64) as i16;
                 }
             }
         }
     }
 
-    // 4/8-neighbors search algorithm
-    pub fn check_cell_neighbors(&mut self, cell: Tile) -> Vec<Tile> {
-        let main_x = cell.get().coordinates.x as i16;
-        let main_y = cell.get().coordinates.y as i16;
-        let mut neighbors: Vec<Tile> = Vec::new();
+    //Lowest cost of a cell that is not blocked, the least any step can cost
+    pub fn min_passable_cost(&self) -> i32 {
+        self.passable_cost_range().0.max(0) as i32
+    }
+
+    //Lowest and highest cost of cells that are not blocked, zeros when every cell is blocked
+    pub fn passable_cost_range(&self) -> (i16, i16) {
+        let costs = self
+            .cells
+            .iter()
+            .flatten()
+            .map(|tile| tile.get())
+            .filter(|cell| *cell.get_state() != CellState::Blocked)
+            .map(|cell| cell.cost);
+        costs.fold(None, |range: Option<(i16, i16)>, cost| match range {
+            Some((min, max)) => Some((min.min(cost), max.max(cost))),
+            None => Some((cost, cost)),
+        })
+        .unwrap_or((0, 0))
+    }
+
+    //Not blocked neighbors, a diagonal step can not cut a blocked corner
+    pub fn passable_neighbors(&self, coordinates: CellCoordinates) -> Vec<(u16, u16)> {
+        self.neighbor_coordinates(coordinates, self.neighborhood)
+            .into_iter()
+            .filter(|&(x, y)| {
+                self.is_valid_to_path(x as i16, y as i16)
+                    && self.is_valid_to_path(x as i16, coordinates.y as i16)
+                    && self.is_valid_to_path(coordinates.x as i16, y as i16)
+            })
+            .collect()
+    }
+
+    //Neighbors inside the field bounds, blocked or not
+    fn neighbor_coordinates(&self, coordinates: CellCoordinates, neighborhood: Neighborhood) -> Vec<(u16, u16)> {
+        let main_x = coordinates.x as i16;
+        let main_y = coordinates.y as i16;
+        let mut neighbors: Vec<(u16, u16)> = Vec::new();
+        // the second step of a side is a corner
+        let steps = match neighborhood {
+            Neighborhood::Four => 1,
+            Neighborhood::Eight => 2,
+        };
 
         //nest cell
         for side in 0..4 {
-            for step in 0..1 {
+            for step in 0..steps {
                 let (x, y) = match side {
                     0 => (main_x + 1, main_y - step),
                     1 => (main_x - step, main_y - 1),
@@ -83,13 +311,13 @@ impl Field {
                     3 => (main_x + step, main_y + 1),
                     _ => (0, 0),
                 };
-                if self.is_valid_coordinates(x, y) && self.is_valid_to_path(x, y) {
-                    neighbors.push(self.make_cell_visited(x, y));
+                if self.is_valid_coordinates(x, y) {
+                    neighbors.push((x as u16, y as u16));
                 }
             }
         }
 
-        return neighbors;
+        neighbors
 
         // It's ok at 3:2
         // It's ok at 3:1
@@ -104,10 +332,4 @@ impl Field {
         //  1;2   2;2    3;2
         //  1;3   2;3    3;3
     }
-
-    fn make_cell_visited(&self, x: i16, y: i16) -> Tile {
-        let current_cell_ref = self.get_cell(x as u16, y as u16);
-        current_cell_ref.get().set_state(CellState::Visited);
-        current_cell_ref
-    }
 }
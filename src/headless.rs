@@ -0,0 +1,115 @@
+use std::fs;
+use std::path::Path;
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+
+use serde::Serialize;
+
+use crate::algorithms::AlgorithmType;
+use crate::cli::OutputFormat;
+use crate::field::Field;
+use crate::scenario::parse_map;
+use crate::settings::Settings;
+use crate::state::{SharedState, State};
+use crate::validation::{optimal_cost, PathVerdict};
+
+//Result of a search run without a window
+#[derive(Serialize)]
+pub struct Report {
+    pub algorithm: &'static str,
+    pub heuristic: &'static str,
+    pub neighborhood: &'static str,
+    pub columns: u16,
+    pub rows: u16,
+    // seed of a generated map, none for a map file
+    pub seed: Option<u64>,
+    pub map: Option<String>,
+    pub found: bool,
+    pub path: Vec<[u16; 2]>,
+    pub path_length: Option<usize>,
+    pub path_cost: Option<i32>,
+    pub optimal_cost: Option<i32>,
+    // path check against the field and the optimal cost, none when no path is found
+    pub verdict: Option<String>,
+    pub expanded: usize,
+    pub runtime_ms: f64,
+}
+
+pub fn load_map(path: &Path, settings: &Settings) -> Result<Field, String> {
+    let content = fs::read_to_string(path).map_err(|error| format!("{}: {}", path.display(), error))?;
+    let mut field = parse_map(&content).map_err(|error| format!("{}: {}", path.display(), error))?;
+    field.neighborhood = settings.neighborhood;
+    Ok(field)
+}
+
+//Search in the calling thread, without delays between steps
+pub fn search(settings: &Settings, algorithm_type: AlgorithmType, field: Field) -> SharedState {
+    let mut settings = *settings;
+    settings.step_delay = 0.0;
+    let state = SharedState::new(State::new(field, &settings));
+    let algorithm = algorithm_type.create(Arc::new(AtomicBool::new(false)));
+    state.get().stats.start();
+    algorithm.lock().unwrap().search(state.clone());
+    state.get().stats.finish();
+    state
+}
+
+pub fn solve(settings: Settings, algorithm_type: AlgorithmType, map: Option<&Path>) -> Result<Report, String> {
+    let field = match map {
+        Some(path) => load_map(path, &settings)?,
+        None => Field::generate(&settings),
+    };
+    let (columns, rows) = (field.columns(), field.rows());
+
+    let state = search(&settings, algorithm_type, field);
+    let mut state = state.get();
+    let path = state.path.clone();
+    let path_cost = state.stats.path_cost;
+    let optimal_cost = optimal_cost(state.field());
+    let verdict = path_cost.map(|cost| PathVerdict::check(state.field(), &path, cost).describe());
+    Ok(Report {
+        algorithm: algorithm_type.name(),
+        heuristic: settings.heuristic.name(),
+        neighborhood: settings.neighborhood.name(),
+        columns,
+        rows,
+        seed: map.is_none().then_some(settings.seed),
+        map: map.map(|path| path.display().to_string()),
+        found: !state.path.is_empty(),
+        path: state.path.iter().map(|coordinates| [coordinates.x, coordinates.y]).collect(),
+        path_length: state.stats.path_length,
+        path_cost,
+        optimal_cost,
+        verdict,
+        expanded: state.stats.expanded,
+        runtime_ms: state.stats.elapsed().as_secs_f64() * 1000.0,
+    })
+}
+
+pub fn print_report(report: &Report, format: OutputFormat) {
+    match format {
+        OutputFormat::Json => println!("{}", serde_json::to_string(report).unwrap()),
+        OutputFormat::Text => {
+            println!("algorithm: {}", report.algorithm);
+            println!("heuristic: {}  neighbors: {}", report.heuristic, report.neighborhood);
+            match (&report.map, report.seed) {
+                (Some(map), _) => println!("map: {} ({}x{})", map, report.columns, report.rows),
+                (None, Some(seed)) => println!("map: seed {} ({}x{})", seed, report.columns, report.rows),
+                (None, None) => {}
+            }
+            println!("expanded: {}", report.expanded);
+            println!("time: {:.3} ms", report.runtime_ms);
+            match (report.path_length, report.path_cost) {
+                (Some(length), Some(cost)) => {
+                    println!("path: {} cells, cost {}", length, cost);
+                    if let Some(verdict) = &report.verdict {
+                        println!("check: {}", verdict);
+                    }
+                    let steps: Vec<String> = report.path.iter().map(|[x, y]| format!("({}, {})", x, y)).collect();
+                    println!("{}", steps.join(" -> "));
+                }
+                _ => println!("path: not found"),
+            }
+        }
+    }
+}
@@ -0,0 +1,20 @@
+pub mod algorithms;
+pub mod app;
+pub mod cell;
+pub mod field;
+pub mod settings;
+pub mod settings_page;
+pub mod state;
+pub mod menu;
+pub mod colors;
+pub mod camera;
+pub mod config;
+pub mod cli;
+pub mod headless;
+pub mod experiment;
+pub mod comparison;
+pub mod scenario;
+pub mod validation;
+pub mod network;
+pub mod network_view;
+pub mod run;
@@ -1,29 +1,54 @@
-use std::sync::{Arc, Mutex};
-
-use piston_window::{Button, Key, MouseCursorEvent, PistonWindow, PressEvent, TextureSettings, WindowSettings};
+use clap::Parser;
+use piston_window::{Button, Key, MouseCursorEvent, MouseScrollEvent, PistonWindow, PressEvent, ReleaseEvent, ResizeEvent, TextureSettings, WindowSettings};
 use piston_window::glyph_cache::rusttype::GlyphCache;
 
-use crate::algorithms::AlgorithmType;
-use crate::menu::{AppMenu, AppState, UnitAppMenu};
-use crate::settings::Settings;
+use pathfinding_algorithms::algorithms::AlgorithmType;
+use pathfinding_algorithms::cli::{Cli, Command};
+use pathfinding_algorithms::config::{heuristic_warning, Config};
+use pathfinding_algorithms::menu::AppMenu;
+use pathfinding_algorithms::network::load_network;
+use pathfinding_algorithms::{experiment, headless};
 
-mod algorithms;
-mod app;
-mod cell;
-mod field;
-mod settings;
-mod state;
-mod menu;
-mod colors;
+// clap exits with 2 on usage errors, so a missing path has its own code
+const NO_PATH_EXIT_CODE: i32 = 3;
 
 fn main() {
-    let settings = Settings::new(30, 30);
+    let cli = Cli::parse();
+    let mut config = Config::load(&cli.config).unwrap_or_else(|error| exit_with_error(&error));
+    config.merge(&cli);
+    let settings = config.settings().unwrap_or_else(|error| exit_with_error(&error));
+    if let Some(warning) = heuristic_warning(&settings) {
+        eprintln!("Warning: {}", warning);
+    }
+
+    if let Some(Command::Headless(args)) = &cli.command {
+        let algorithm_type = config.search.algorithm
+            .unwrap_or_else(|| exit_with_error("headless mode needs an algorithm (--algorithm)"));
+        let report = headless::solve(settings, algorithm_type, args.map.as_deref())
+            .unwrap_or_else(|error| fail("Can not load the map", &error));
+        headless::print_report(&report, args.format);
+        // scripts can tell a missing path by the exit code
+        std::process::exit(if report.found { 0 } else { NO_PATH_EXIT_CODE });
+    }
+    if let Some(Command::Experiment(args)) = &cli.command {
+        experiment::run(settings, args).unwrap_or_else(|error| fail("Experiment failed", &error));
+        return;
+    }
+
+    // a graph is loaded before the window is opened, so its errors are reported right away
+    let network = cli.graph.as_ref().map(|graph| {
+        let mut network = load_network(graph, cli.coordinates.as_deref())
+            .unwrap_or_else(|error| fail("Can not load the graph", &error));
+        network.set_endpoints(cli.from.as_deref(), cli.to.as_deref())
+            .unwrap_or_else(|error| exit_with_error(&error));
+        network
+    });
 
     let mut window: PistonWindow = WindowSettings::new(
         "Pathfinders test",
         [settings.window_size.raw_x, settings.window_size.raw_y],
     )
-        .resizable(false)
+        .resizable(true)
         .vsync(true)
         .build()
         .expect("Can not create a main window!");
@@ -36,17 +61,12 @@ fn main() {
         )
         .expect("Can not load font!");
 
-    let mut app_menu = AppMenu::new(
-        35,
-        settings,
-        vec![
-            UnitAppMenu::new("BFS", AlgorithmType::BFS),
-            UnitAppMenu::new("DFS", AlgorithmType::DFS),
-            UnitAppMenu::new("GBFS", AlgorithmType::GBFS),
-            UnitAppMenu::new("DIJKSTRA", AlgorithmType::Dijkstra),
-            UnitAppMenu::new("A_STAR", AlgorithmType::AStar),
-        ],
-    );
+    let mut app_menu = AppMenu::new(settings, config.theme.colors, cli.config.clone());
+    if let Some(network) = network {
+        app_menu.open_network(config.search.algorithm.unwrap_or(AlgorithmType::AStar), network);
+    } else if let Some(algorithm_type) = config.search.algorithm {
+        app_menu.run_algorithm(algorithm_type);
+    }
 
     while let Some(event) = window.next() {
         window.draw_2d(&event, |context, graphical_buffer, device| {
@@ -63,27 +83,33 @@ fn main() {
                 Button::Keyboard(key) => {
                     match key {
                         Key::Escape => app_menu.back_to_menu(),
-                        Key::D1 | Key::NumPad1 => {
-                            app_menu.app_state = AppState::Algorithm;
-                            app_menu.app = Some(Arc::new(Mutex::new(app_menu.items.get(0).unwrap().run(app_menu.settings))));
-                        }
-                        Key::D2 | Key::NumPad2 => {
-                            app_menu.app_state = AppState::Algorithm;
-                            app_menu.app = Some(Arc::new(Mutex::new(app_menu.items.get(1).unwrap().run(app_menu.settings))));
-                        }
-                        Key::D3 | Key::NumPad3 => {
-                            app_menu.app_state = AppState::Algorithm;
-                            app_menu.app = Some(Arc::new(Mutex::new(app_menu.items.get(2).unwrap().run(app_menu.settings))));
+                        Key::F => app_menu.fit_to_window(),
+                        Key::G => app_menu.toggle_cost_gradient(),
+                        Key::H => app_menu.toggle_hud(),
+                        Key::L => app_menu.cycle_cell_label(),
+                        Key::N => app_menu.toggle_minimap(),
+                        Key::P => app_menu.toggle_parents(),
+                        Key::T => app_menu.cycle_theme(),
+                        Key::R => app_menu.randomize_seed(),
+                        Key::V => app_menu.toggle_ensure_solvable(),
+                        Key::Equals | Key::NumPadPlus => app_menu.step_seed(1),
+                        Key::Minus | Key::NumPadMinus => app_menu.step_seed(-1),
+                        Key::M => app_menu.open_scenarios(),
+                        Key::C => app_menu.start_comparison(),
+                        Key::K => app_menu.toggle_keep_map(),
+                        Key::S => app_menu.open_settings(),
+                        Key::Left => app_menu.change_setting(-1),
+                        Key::Right => app_menu.change_setting(1),
+                        Key::Up => app_menu.move_focus(-1),
+                        Key::Down => app_menu.move_focus(1),
+                        Key::PageUp => app_menu.move_focus_by_page(-1),
+                        Key::PageDown => app_menu.move_focus_by_page(1),
+                        Key::Return | Key::NumPadEnter => app_menu.activate_focused(),
+                        _ => {
+                            if let Some(number) = item_number(key) {
+                                app_menu.select_item(number);
+                            }
                         }
-                        Key::D4 | Key::NumPad4 => {
-                            app_menu.app_state = AppState::Algorithm;
-                            app_menu.app = Some(Arc::new(Mutex::new(app_menu.items.get(3).unwrap().run(app_menu.settings))));
-                        }
-                        Key::D5 | Key::NumPad5 => {
-                            app_menu.app_state = AppState::Algorithm;
-                            app_menu.app = Some(Arc::new(Mutex::new(app_menu.items.get(4).unwrap().run(app_menu.settings))));
-                        }
-                        _ => {}
                     }
                 }
                 Button::Mouse(mouse_button) => {
@@ -94,8 +120,42 @@ fn main() {
             }
         }
 
+        if let Some(Button::Mouse(mouse_button)) = event.release_args() {
+            app_menu.on_mouse_release(&mouse_button);
+        }
+
         if let Some(move_args) = event.mouse_cursor_args() {
             app_menu.on_mouse_move(&move_args);
         }
+
+        if let Some(scroll_args) = event.mouse_scroll_args() {
+            app_menu.on_mouse_scroll(&scroll_args);
+        }
+
+        if let Some(resize_args) = event.resize_args() {
+            app_menu.on_resize(&resize_args.window_size);
+        }
     }
+    // settings changes are saved and search threads are stopped when the window is closed
+    app_menu.back_to_menu();
+}
+
+//Number keys pick menu items from the top, in the menu order (grouped by category)
+fn item_number(key: Key) -> Option<usize> {
+    let digits = [Key::D1, Key::D2, Key::D3, Key::D4, Key::D5, Key::D6, Key::D7, Key::D8, Key::D9];
+    let numpad = [
+        Key::NumPad1, Key::NumPad2, Key::NumPad3, Key::NumPad4, Key::NumPad5,
+        Key::NumPad6, Key::NumPad7, Key::NumPad8, Key::NumPad9,
+    ];
+    digits.iter().position(|&digit| digit == key)
+        .or_else(|| numpad.iter().position(|&digit| digit == key))
+}
+
+fn exit_with_error(error: &str) -> ! {
+    fail("Invalid configuration", error)
+}
+
+fn fail(kind: &str, error: &str) -> ! {
+    eprintln!("{}: {}", kind, error);
+    std::process::exit(1);
 }
@@ -1,35 +1,84 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
-use std::sync::atomic::Ordering;
+use std::time::Duration;
 
 use piston_window::{CharacterCache, clear, Context, DrawState, G2d, Glyphs, MouseButton, rectangle, text, Transformed};
-use piston_window::types::FontSize;
+use piston_window::types::{Color, FontSize};
 
+use crate::algorithms::registry::{AlgorithmInfo, Category, ALGORITHMS};
 use crate::algorithms::AlgorithmType;
 use crate::app::App;
+use crate::colors::Theme;
+use crate::comparison::Comparison;
+use crate::field::Field;
+use crate::network::Network;
+use crate::network_view::NetworkView;
+use crate::run::RunManager;
+use crate::scenario::SCENARIOS;
 use crate::settings::{Settings, Vec2f};
+use crate::settings_page::{Setting, SettingsPage};
 
 pub enum AppState {
     Menu,
+    Scenarios,
+    Settings,
     Algorithm,
+    Comparison,
+    Network,
+}
+
+#[derive(Copy, Clone)]
+pub enum MenuAction {
+    RunAlgorithm(AlgorithmType),
+    // None is a random map generated from the seed
+    SelectScenario(Option<usize>),
 }
 
 pub struct AppMenu {
     pub(crate) app_state: AppState,
     pub(crate) app: Option<Arc<Mutex<App>>>,
+    comparison: Option<Comparison>,
+    network: Option<NetworkView>,
+    // run the next algorithm on the map of the previous one
+    keep_map: bool,
     font_size: FontSize,
     pub(crate) settings: Settings,
-    pub items: Vec<UnitAppMenu>,
+    items: MenuList,
+    // config colors applied to every theme
+    theme_colors: HashMap<String, Color>,
+    scenario_items: MenuList,
+    settings_page: SettingsPage,
+    runs: RunManager,
     mouse_coordinates: Vec2f,
 }
 
 impl AppMenu {
-    pub fn new(font_size: FontSize, settings: Settings, items: Vec<UnitAppMenu>) -> Self {
+    //Settings changed on the settings page are saved to the config file
+    pub fn new(settings: Settings, theme_colors: HashMap<String, Color>, config_path: PathBuf) -> Self {
         AppMenu {
             app_state: AppState::Menu,
             app: None,
-            font_size,
+            comparison: None,
+            network: None,
+            keep_map: false,
+            font_size: settings.menu_font_size,
             settings,
-            items,
+            items: MenuList::algorithms(),
+            theme_colors,
+            scenario_items: MenuList::new(
+                std::iter::once(UnitAppMenu::scenario("Random", None))
+                    .chain(
+                        SCENARIOS
+                            .iter()
+                            .enumerate()
+                            .map(|(index, scenario)| UnitAppMenu::scenario(scenario.name, Some(index))),
+                    )
+                    .collect(),
+                Vec::new(),
+            ),
+            settings_page: SettingsPage::new(config_path),
+            runs: RunManager::new(STOP_TIMEOUT),
             mouse_coordinates: Vec2f { raw_x: 0.0, raw_y: 0.0 },
         }
     }
@@ -42,30 +91,74 @@ impl AppMenu {
     ) {
         match &self.app_state {
             AppState::Menu => {
-                clear(piston_window::color::GRAY, g2d);
+                clear(self.settings.theme.menu_background, g2d);
 
                 let preview_text = "Select algorithm: ";
-                let preview_text_width = glyphs.width(self.font_size, preview_text).unwrap();
-                text::Text::new_color(piston_window::color::BLACK, self.font_size)
-                    .draw(
-                        preview_text,
-                        glyphs,
-                        &DrawState::default(),
-                        context.transform.trans((self.settings.window_size.raw_x - preview_text_width) / 2.0, 100.0),
-                        g2d,
-                    )
-                    .unwrap();
+                self.render_title(preview_text, context, g2d, glyphs);
 
-                let padding = 20.0;// between units
-                let mut temp = 70.0;// upper padding
-                for item in self.items.iter_mut() {
-                    item.width = self.settings.window_size.raw_x / 2.0;
-                    item.height = self.settings.window_size.raw_y / 10.0;
-                    temp += item.height + padding;
-                    item.x = self.settings.window_size.raw_x / 2.0;
-                    item.y = temp;
-                    item.render(context, g2d, glyphs, self.font_size);
+                let map_name = match self.settings.scenario {
+                    Some(index) => SCENARIOS[index].name,
+                    None => "Random",
+                };
+                let mut hints = vec![
+                    format!("Seed: {} (R - random, +/- to change)", self.settings.seed),
+                    format!(
+                        "Solvable maps only: {} (V to toggle)",
+                        if self.settings.ensure_solvable { "on" } else { "off" }
+                    ),
+                    format!("Map: {} (M to choose)", map_name),
+                    format!(
+                        "Keep current map: {} (K to toggle)",
+                        if self.keep_map { "on" } else { "off" }
+                    ),
+                    "Right click to mark algorithms, C to compare them".to_string(),
+                    format!("Theme: {} (T to change, S for all settings)", self.settings.theme.kind.name()),
+                ];
+                if let Some(report) = &self.runs.last_report {
+                    hints.push(format!("Previous run: {}", report));
                 }
+                hints.extend(self.settings_page.message.clone());
+                self.render_hints(&hints, context, g2d, glyphs);
+
+                self.items.layout(self.settings.window_size, hints.len(), self.font_size);
+                self.items.render(context, g2d, glyphs, self.font_size, &self.settings.theme, self.mouse_coordinates);
+            }
+            AppState::Scenarios => {
+                clear(self.settings.theme.menu_background, g2d);
+                self.render_title("Select map: ", context, g2d, glyphs);
+                let hints = ["Esc to go back".to_string()];
+                self.render_hints(&hints, context, g2d, glyphs);
+
+                self.scenario_items.layout(self.settings.window_size, hints.len(), self.font_size);
+                self.scenario_items.render(
+                    context,
+                    g2d,
+                    glyphs,
+                    self.font_size,
+                    &self.settings.theme,
+                    self.mouse_coordinates,
+                );
+            }
+            AppState::Settings => {
+                clear(self.settings.theme.menu_background, g2d);
+                self.render_title("Settings: ", context, g2d, glyphs);
+                let hints = [
+                    "Up/Down to choose, Left/Right or < > to change".to_string(),
+                    "Esc to go back, changes are saved to the config file then".to_string(),
+                ];
+                self.render_hints(&hints, context, g2d, glyphs);
+
+                let bottom = self.settings.window_size.raw_y - hints.len() as f64 * HINT_HEIGHT - ITEM_PADDING;
+                self.settings_page.render(
+                    &self.settings,
+                    LIST_TOP,
+                    bottom,
+                    self.font_size,
+                    self.mouse_coordinates,
+                    context,
+                    g2d,
+                    glyphs,
+                );
             }
             AppState::Algorithm => {
                 if let Some(app_ref) = &self.app {
@@ -73,75 +166,673 @@ impl AppMenu {
                     app.render(context, g2d, glyphs);
                 };
             }
+            AppState::Comparison => {
+                if let Some(comparison) = &mut self.comparison {
+                    comparison.render(context, g2d, glyphs);
+                }
+            }
+            AppState::Network => {
+                if let Some(network) = &mut self.network {
+                    network.render(context, g2d, glyphs);
+                }
+            }
+        }
+    }
+
+    fn render_title(&self, preview_text: &str, context: Context, g2d: &mut G2d, glyphs: &mut Glyphs) {
+        let preview_text_width = glyphs.width(self.font_size, preview_text).unwrap();
+        text::Text::new_color(self.settings.theme.text, self.font_size)
+            .draw(
+                preview_text,
+                glyphs,
+                &DrawState::default(),
+                context.transform.trans((self.settings.window_size.raw_x - preview_text_width) / 2.0, 100.0),
+                g2d,
+            )
+            .unwrap();
+    }
+
+    //Hints are drawn at the bottom of the window
+    fn render_hints(&self, hints: &[String], context: Context, g2d: &mut G2d, glyphs: &mut Glyphs) {
+        let hint_font_size = self.font_size / 2;
+        for (n, hint) in hints.iter().enumerate() {
+            let hint_width = glyphs.width(hint_font_size, hint).unwrap();
+            text::Text::new_color(self.settings.theme.text, hint_font_size)
+                .draw(
+                    hint,
+                    glyphs,
+                    &DrawState::default(),
+                    context.transform.trans(
+                        (self.settings.window_size.raw_x - hint_width) / 2.0,
+                        self.settings.window_size.raw_y - (hints.len() - n) as f64 * HINT_HEIGHT + 20.0,
+                    ),
+                    g2d,
+                )
+                .unwrap();
         }
     }
 
+    //The last app is kept with its field, so its map can be reused
     pub fn back_to_menu(&mut self) {
+        if let AppState::Settings = self.app_state {
+            self.settings_page.save(&self.settings);
+        }
+        self.stop_runs();
+        self.comparison = None;
+        self.network = None;
+        self.app_state = AppState::Menu;
+    }
+
+    //Cancel every search thread and wait for them, how they ended is shown in the menu
+    fn stop_runs(&mut self) {
+        let mut runs = Vec::new();
         if let Some(app_ref) = &self.app {
-            let app_guard = app_ref.lock().unwrap();
-            app_guard.should_stop.store(true, Ordering::Relaxed);
+            runs.extend(app_ref.lock().unwrap().take_run());
+        }
+        if let Some(comparison) = &mut self.comparison {
+            runs.extend(comparison.take_runs());
+        }
+        if let Some(network) = &mut self.network {
+            runs.extend(network.take_run());
+        }
+        self.runs.stop(runs);
+    }
+
+    //New searches start only when no old thread is left, so at most the threads of one screen are alive
+    fn prepare_run(&mut self) -> bool {
+        self.stop_runs();
+        if self.runs.lingering() > 0 {
+            self.runs.last_report = Some("the previous search is still stopping, try again".to_string());
             self.app_state = AppState::Menu;
+            return false;
+        }
+        true
+    }
+
+    pub fn open_scenarios(&mut self) {
+        if let AppState::Menu = self.app_state {
+            self.app_state = AppState::Scenarios;
+        }
+    }
+
+    pub fn open_settings(&mut self) {
+        if let AppState::Menu = self.app_state {
+            self.settings_page.message = None;
+            self.app_state = AppState::Settings;
+        }
+    }
+
+    //Step the focused setting on the settings page and save it
+    pub fn change_setting(&mut self, step: i32) {
+        if let AppState::Settings = self.app_state {
+            let setting = self.settings_page.focused();
+            setting.change(&mut self.settings, step, &self.theme_colors);
+            if let Setting::Theme = setting {
+                self.set_theme(self.settings.theme);
+            }
+            self.settings_page.mark_changed(setting);
+        }
+    }
+
+    pub fn toggle_keep_map(&mut self) {
+        if let AppState::Menu = self.app_state {
+            self.keep_map = !self.keep_map;
+        }
+    }
+
+    //Copy of the last algorithm field without its search overlay, if the map is kept
+    fn current_field(&self) -> Option<Field> {
+        if !self.keep_map {
+            return None;
+        }
+        let app = self.app.as_ref()?.lock().unwrap();
+        let mut state = app.state().get();
+        let mut field = state.field().duplicate();
+        field.reset_search();
+        Some(field)
+    }
+
+    //Run marked algorithms (or all of them) on the same field
+    pub fn start_comparison(&mut self) {
+        if let AppState::Menu = self.app_state {
+            let marked: Vec<AlgorithmType> = self.items.items.iter()
+                .filter(|item| item.marked)
+                .filter_map(|item| item.algorithm_type())
+                .collect();
+            let algorithm_types = if marked.is_empty() {
+                self.items.items.iter().filter_map(|item| item.algorithm_type()).collect()
+            } else {
+                marked
+            };
+            if !self.prepare_run() {
+                return;
+            }
+            let comparison = match self.current_field() {
+                Some(field) => Comparison::with_field(self.settings, &algorithm_types, field),
+                None => Comparison::new(self.settings, &algorithm_types),
+            };
+            self.comparison = Some(comparison);
+            self.app_state = AppState::Comparison;
+        }
+    }
+
+    //Item list of the current page
+    fn menu_list(&mut self) -> Option<&mut MenuList> {
+        match self.app_state {
+            AppState::Scenarios => Some(&mut self.scenario_items),
+            AppState::Menu => Some(&mut self.items),
+            AppState::Settings | AppState::Algorithm | AppState::Comparison | AppState::Network => None,
+        }
+    }
+
+    //Activate item by its number in the list
+    pub fn select_item(&mut self, index: usize) {
+        let items = match self.app_state {
+            AppState::Scenarios => &self.scenario_items,
+            AppState::Menu | AppState::Algorithm => &self.items,
+            AppState::Settings | AppState::Comparison | AppState::Network => return,
         };
+        if let Some(item) = items.items.get(index) {
+            self.activate(item.action);
+        }
+    }
+
+    //Arrow keys move the focus by one item, page keys by a visible page
+    pub fn move_focus(&mut self, step: isize) {
+        if let AppState::Settings = self.app_state {
+            self.settings_page.move_focus(step);
+        }
+        if let Some(list) = self.menu_list() {
+            list.move_focus(step);
+        }
+    }
+
+    pub fn move_focus_by_page(&mut self, pages: isize) {
+        if let Some(list) = self.menu_list() {
+            list.move_focus(pages * list.page_size());
+        }
+    }
+
+    pub fn activate_focused(&mut self) {
+        let action = self.menu_list()
+            .and_then(|list| Some(list.items[list.focus?].action));
+        if let Some(action) = action {
+            self.activate(action);
+        }
+    }
+
+    //Skip the menu and run the algorithm right away
+    pub fn run_algorithm(&mut self, algorithm_type: AlgorithmType) {
+        self.activate(MenuAction::RunAlgorithm(algorithm_type));
+    }
+
+    //Search a loaded graph instead of a grid
+    pub fn open_network(&mut self, algorithm_type: AlgorithmType, network: Network) {
+        if !self.prepare_run() {
+            return;
+        }
+        let mut view = NetworkView::new(self.settings, algorithm_type, network);
+        view.start();
+        self.network = Some(view);
+        self.app_state = AppState::Network;
+    }
+
+    fn activate(&mut self, action: MenuAction) {
+        match action {
+            MenuAction::RunAlgorithm(algorithm_type) => {
+                if !self.prepare_run() {
+                    return;
+                }
+                let mut app = match self.current_field() {
+                    Some(field) => App::with_field(self.settings, &algorithm_type, field),
+                    None => App::new(self.settings, &algorithm_type),
+                };
+                app.start();
+                self.app_state = AppState::Algorithm;
+                self.app = Some(Arc::new(Mutex::new(app)));
+            }
+            MenuAction::SelectScenario(scenario) => {
+                self.settings.scenario = scenario;
+                self.app_state = AppState::Menu;
+            }
+        }
+    }
+
+    pub fn randomize_seed(&mut self) {
+        if let AppState::Menu = self.app_state {
+            self.settings.seed = rand::random();
+        }
+    }
+
+    pub fn step_seed(&mut self, step: i64) {
+        if let AppState::Menu = self.app_state {
+            self.settings.seed = self.settings.seed.wrapping_add_signed(step);
+        }
+    }
+
+    pub fn toggle_ensure_solvable(&mut self) {
+        if let AppState::Menu = self.app_state {
+            self.settings.ensure_solvable = !self.settings.ensure_solvable;
+        }
+    }
+
+    pub fn toggle_hud(&mut self) {
+        self.settings.show_hud = !self.settings.show_hud;
+        if let Some(app_ref) = &self.app {
+            app_ref.lock().unwrap().set_show_hud(self.settings.show_hud);
+        }
+        if let Some(network) = &mut self.network {
+            network.set_show_hud(self.settings.show_hud);
+        }
+    }
+
+    pub fn cycle_cell_label(&mut self) {
+        self.settings.cell_label = self.settings.cell_label.next();
+        if let Some(app_ref) = &self.app {
+            app_ref.lock().unwrap().set_cell_label(self.settings.cell_label);
+        }
+    }
+
+    pub fn toggle_parents(&mut self) {
+        self.settings.show_parents = !self.settings.show_parents;
+        if let Some(app_ref) = &self.app {
+            app_ref.lock().unwrap().set_show_parents(self.settings.show_parents);
+        }
+    }
+
+    pub fn toggle_minimap(&mut self) {
+        self.settings.show_minimap = !self.settings.show_minimap;
+        if let Some(app_ref) = &self.app {
+            app_ref.lock().unwrap().set_show_minimap(self.settings.show_minimap);
+        }
+    }
+
+    pub fn cycle_theme(&mut self) {
+        let kind = self.settings.theme.kind.next();
+        let theme = kind.theme()
+            .with_overrides(&self.theme_colors)
+            .expect("Theme colors are validated on start!");
+        self.set_theme(theme);
+    }
+
+    fn set_theme(&mut self, theme: Theme) {
+        self.settings.theme = theme;
+        if let Some(app_ref) = &self.app {
+            app_ref.lock().unwrap().set_theme(theme);
+        }
+        if let Some(comparison) = &mut self.comparison {
+            comparison.set_theme(theme);
+        }
+        if let Some(network) = &mut self.network {
+            network.set_theme(theme);
+        }
+    }
+
+    pub fn toggle_cost_gradient(&mut self) {
+        self.settings.cost_gradient = !self.settings.cost_gradient;
+        if let Some(app_ref) = &self.app {
+            app_ref.lock().unwrap().set_cost_gradient(self.settings.cost_gradient);
+        }
     }
 
     pub fn on_mouse_click(&mut self, button: &MouseButton) {
+        if let (AppState::Network, Some(network)) = (&self.app_state, &mut self.network) {
+            network.on_mouse_click(button);
+            return;
+        }
+        if let AppState::Algorithm = self.app_state {
+            if let Some(app_ref) = &self.app {
+                app_ref.lock().unwrap().on_mouse_click(button);
+            }
+            return;
+        }
+
+        let (x, y) = (self.mouse_coordinates.raw_x, self.mouse_coordinates.raw_y);
+        if let (&MouseButton::Left, AppState::Settings) = (button, &self.app_state) {
+            if let Some((row, step)) = self.settings_page.control_at(x, y) {
+                self.settings_page.focus = row;
+                if step != 0 {
+                    self.change_setting(step);
+                }
+            }
+            return;
+        }
+        if let (&MouseButton::Right, AppState::Menu) = (button, &self.app_state) {
+            if let Some(index) = self.items.item_at(x, y) {
+                let item = &mut self.items.items[index];
+                item.marked = !item.marked;
+            }
+            return;
+        }
+
         if let &MouseButton::Left = button {
-            let x = self.mouse_coordinates.raw_x;
-            let y = self.mouse_coordinates.raw_y;
-            self.items.iter()
-                .filter(|item| item.is_hovered(x, y))
-                .map(|item| {
-                    self.app_state = AppState::Algorithm;
-                    self.app = Some(Arc::new(Mutex::new(item.run(self.settings))))
-                })
-                .count();
+            let action = self.menu_list()
+                .and_then(|list| list.item_at(x, y).map(|index| list.items[index].action));
+            if let Some(action) = action {
+                self.activate(action);
+            }
+        }
+    }
+
+    //The kept app only sees the mouse while it is shown
+    pub fn on_mouse_release(&mut self, button: &MouseButton) {
+        if let (AppState::Algorithm, Some(app_ref)) = (&self.app_state, &self.app) {
+            app_ref.lock().unwrap().on_mouse_release(button);
+        }
+        if let (AppState::Network, Some(network)) = (&self.app_state, &mut self.network) {
+            network.on_mouse_release(button);
+        }
+    }
+
+    pub fn on_mouse_scroll(&mut self, args: &[f64; 2]) {
+        let font_size = self.font_size;
+        if let Some(list) = self.menu_list() {
+            // a wheel step scrolls by about a line of text
+            list.scroll_by(-args[1] * font_size as f64 * 2.0);
+        }
+        if let (AppState::Algorithm, Some(app_ref)) = (&self.app_state, &self.app) {
+            app_ref.lock().unwrap().zoom(args[1]);
+        }
+        if let (AppState::Network, Some(network)) = (&self.app_state, &mut self.network) {
+            network.zoom(args[1]);
+        }
+    }
+
+    pub fn fit_to_window(&mut self) {
+        if let (AppState::Algorithm, Some(app_ref)) = (&self.app_state, &self.app) {
+            app_ref.lock().unwrap().fit_to_window();
+        }
+        if let (AppState::Network, Some(network)) = (&self.app_state, &mut self.network) {
+            network.fit_to_window();
+        }
+    }
+
+    pub fn on_resize(&mut self, window_size: &[f64; 2]) {
+        self.settings.window_size = Vec2f {
+            raw_x: window_size[0],
+            raw_y: window_size[1],
+        };
+        if let Some(app_ref) = &self.app {
+            app_ref.lock().unwrap().set_window_size(self.settings.window_size);
+        }
+        if let Some(comparison) = &mut self.comparison {
+            comparison.set_window_size(self.settings.window_size);
+        }
+        if let Some(network) = &mut self.network {
+            network.set_window_size(self.settings.window_size);
         }
     }
 
     pub fn on_mouse_move(&mut self, args: &[f64; 2]) {
         self.mouse_coordinates.raw_x = args[0];
         self.mouse_coordinates.raw_y = args[1];
+        if let (AppState::Algorithm, Some(app_ref)) = (&self.app_state, &self.app) {
+            app_ref.lock().unwrap().on_mouse_move(args);
+        }
+        if let (AppState::Network, Some(network)) = (&self.app_state, &mut self.network) {
+            network.on_mouse_move(args);
+        }
+    }
+}
+
+const HINT_HEIGHT: f64 = 30.0;
+// time stopped searches get to end their threads
+const STOP_TIMEOUT: Duration = Duration::from_secs(1);
+
+// space above the list, the title is drawn there
+const LIST_TOP: f64 = 120.0;
+const ITEM_PADDING: f64 = 10.0;
+
+//Column of items with category headers between the title and the hints.
+//It scrolls when the items do not fit, the keyboard moves the focus
+pub struct MenuList {
+    items: Vec<UnitAppMenu>,
+    // headers are drawn above the items with these indices
+    headers: Vec<(usize, &'static str)>,
+    // pixels the list is scrolled down by
+    scroll: f64,
+    focus: Option<usize>,
+    // layout of the last frame: visible part [top; bottom) and the list height
+    top: f64,
+    bottom: f64,
+    content_height: f64,
+    header_height: f64,
+}
+
+impl MenuList {
+    //Items are numbered for the number keys
+    fn new(mut items: Vec<UnitAppMenu>, headers: Vec<(usize, &'static str)>) -> MenuList {
+        for (n, item) in items.iter_mut().enumerate().take(9) {
+            item.label = format!("{}. {}", n + 1, item.label);
+        }
+        MenuList {
+            items,
+            headers,
+            scroll: 0.0,
+            focus: None,
+            top: 0.0,
+            bottom: 0.0,
+            content_height: 0.0,
+            header_height: 0.0,
+        }
+    }
+
+    //Algorithms grouped by category, empty categories get no header
+    fn algorithms() -> MenuList {
+        let mut items = Vec::new();
+        let mut headers = Vec::new();
+        for category in Category::ALL {
+            let start = items.len();
+            items.extend(
+                ALGORITHMS
+                    .iter()
+                    .filter(|info| info.category == category)
+                    .map(UnitAppMenu::new),
+            );
+            if items.len() > start {
+                headers.push((start, category.name()));
+            }
+        }
+        MenuList::new(items, headers)
+    }
+
+    //Place items, they keep their height and the list scrolls instead of shrinking them
+    fn layout(&mut self, window_size: Vec2f, hints_number: usize, font_size: FontSize) {
+        self.top = LIST_TOP;
+        self.bottom = (window_size.raw_y - hints_number as f64 * HINT_HEIGHT - ITEM_PADDING).max(self.top);
+        let item_height = (window_size.raw_y / 10.0).max(font_size as f64 * 2.0);
+        self.header_height = font_size as f64 * 0.9;
+
+        let mut y = 0.0;
+        for (n, item) in self.items.iter_mut().enumerate() {
+            if self.headers.iter().any(|&(index, _)| index == n) {
+                y += self.header_height;
+            }
+            item.width = window_size.raw_x / 2.0;
+            item.height = item_height;
+            item.x = window_size.raw_x / 4.0;
+            item.y = y;
+            y += item_height + ITEM_PADDING;
+        }
+        self.content_height = y;
+        self.scroll_by(0.0);
+        for item in self.items.iter_mut() {
+            item.y += self.top - self.scroll;
+        }
+    }
+
+    fn scroll_by(&mut self, pixels: f64) {
+        let max_scroll = (self.content_height - (self.bottom - self.top)).max(0.0);
+        self.scroll = (self.scroll + pixels).clamp(0.0, max_scroll);
+    }
+
+    //Only items that fit the visible part whole are drawn and can be clicked
+    fn is_visible(&self, item: &UnitAppMenu) -> bool {
+        item.y >= self.top && item.y + item.height <= self.bottom
+    }
+
+    fn item_at(&self, x: f64, y: f64) -> Option<usize> {
+        self.items
+            .iter()
+            .position(|item| self.is_visible(item) && item.is_hovered(x, y))
+    }
+
+    //Move the focus by the number of items and scroll to it
+    fn move_focus(&mut self, step: isize) {
+        if self.items.is_empty() {
+            return;
+        }
+        let focus = match self.focus {
+            Some(focus) => focus.saturating_add_signed(step).min(self.items.len() - 1),
+            None => 0,
+        };
+        self.focus = Some(focus);
+        let item = &self.items[focus];
+        let (item_top, item_bottom) = (item.y, item.y + item.height);
+        // the header of the first item of a category is shown with it
+        let header = match self.headers.iter().any(|&(index, _)| index == focus) {
+            true => self.header_height,
+            false => 0.0,
+        };
+        if item_top - header < self.top {
+            self.scroll_by(item_top - header - self.top);
+        } else if item_bottom > self.bottom {
+            self.scroll_by(item_bottom - self.bottom);
+        }
+    }
+
+    //Items that fit the visible part at once
+    fn page_size(&self) -> isize {
+        let item_height = self.items.first().map_or(1.0, |item| item.height + ITEM_PADDING);
+        (((self.bottom - self.top) / item_height) as isize).max(1)
+    }
+
+    fn render(
+        &mut self,
+        context: Context,
+        g2d: &mut G2d,
+        glyphs: &mut Glyphs,
+        font_size: FontSize,
+        theme: &Theme,
+        mouse: Vec2f,
+    ) {
+        let hovered = self.item_at(mouse.raw_x, mouse.raw_y);
+        let header_size = font_size / 2;
+        for &(index, header) in self.headers.iter() {
+            let item = &self.items[index];
+            let header_y = item.y - ITEM_PADDING;
+            if header_y - header_size as f64 >= self.top && item.y + item.height <= self.bottom {
+                text::Text::new_color(theme.text, header_size)
+                    .draw(
+                        &header.to_uppercase(),
+                        glyphs,
+                        &DrawState::default(),
+                        context.transform.trans(item.x, header_y),
+                        g2d,
+                    )
+                    .unwrap();
+            }
+        }
+        for n in 0..self.items.len() {
+            if self.is_visible(&self.items[n]) {
+                let focused = self.focus == Some(n);
+                self.items[n].render(context, g2d, glyphs, font_size, theme, hovered == Some(n), focused);
+            }
+        }
+
+        // scroll bar when the list does not fit
+        let visible_height = self.bottom - self.top;
+        if self.content_height > visible_height {
+            if let Some(item) = self.items.first() {
+                let bar_x = item.x + item.width + ITEM_PADDING;
+                let bar_height = visible_height * visible_height / self.content_height;
+                let bar_y = self.top + (visible_height - bar_height) * self.scroll
+                    / (self.content_height - visible_height);
+                rectangle(theme.menu_item, [bar_x, self.top, 4.0, visible_height], context.transform, g2d);
+                rectangle(theme.focus_frame, [bar_x, bar_y, 4.0, bar_height], context.transform, g2d);
+            }
+        }
     }
 }
 
 pub struct UnitAppMenu {
     label: String,
+    // smaller second line under the label
+    description: Option<&'static str>,
     x: f64,
     y: f64,
     width: f64,
     height: f64,
-    algorithm_type: AlgorithmType,
+    action: MenuAction,
+    // marked algorithms are compared side by side
+    marked: bool,
 }
 
 impl UnitAppMenu {
-    pub fn new(label: &str, algorithm_type: AlgorithmType) -> Self {
+    pub fn new(info: &AlgorithmInfo) -> Self {
+        UnitAppMenu {
+            label: info.name.to_string(),
+            description: Some(info.description),
+            x: 0.0,
+            y: 0.0,
+            width: 0.0,
+            height: 0.0,
+            action: MenuAction::RunAlgorithm(info.algorithm_type),
+            marked: false,
+        }
+    }
+
+    pub fn scenario(label: &str, scenario: Option<usize>) -> Self {
         UnitAppMenu {
             label: label.to_string(),
+            description: None,
             x: 0.0,
             y: 0.0,
             width: 0.0,
             height: 0.0,
-            algorithm_type,
+            action: MenuAction::SelectScenario(scenario),
+            marked: false,
         }
     }
 
-    pub fn run(&self, settings: Settings) -> App {
-        let mut app = App::new(settings.clone(), &self.algorithm_type);
-        app.start();
-        return app;
+    fn algorithm_type(&self) -> Option<AlgorithmType> {
+        match self.action {
+            MenuAction::RunAlgorithm(algorithm_type) => Some(algorithm_type),
+            MenuAction::SelectScenario(_) => None,
+        }
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn render(
         &mut self,
         context: Context,
         g2d: &mut G2d,
         glyphs: &mut Glyphs,
         font_size: FontSize,
+        theme: &Theme,
+        hovered: bool,
+        focused: bool,
     ) {
-        let unit_x = self.x - (self.width / 2.0);
+        let unit_x = self.x;
         let unit_y = self.y;
+        let color = if self.marked {
+            theme.marked_menu_item
+        } else if hovered {
+            theme.hovered_menu_item
+        } else {
+            theme.menu_item
+        };
+        if focused {
+            let frame = 3.0;
+            rectangle(
+                theme.focus_frame,
+                [unit_x - frame, unit_y - frame, self.width + frame * 2.0, self.height + frame * 2.0],
+                context.transform,
+                g2d,
+            );
+        }
         rectangle(
-            piston_window::color::CYAN,
+            color,
             [
                 unit_x,
                 unit_y,
@@ -154,18 +845,43 @@ impl UnitAppMenu {
         let transform = context.transform.trans(unit_x, unit_y);
         let unit_text = &*self.label;
         let text_width = glyphs.width(font_size, unit_text).unwrap();
-        text::Text::new_color(piston_window::color::BLACK, font_size)
+        // the description is shown only when the item is high enough for two lines
+        let description = self.description.filter(|_| self.height >= font_size as f64 * 1.8);
+        let label_y = match description {
+            Some(_) => self.height / 2.0,
+            None => (self.height + font_size as f64 * 0.75) / 2.0,
+        };
+        text::Text::new_color(theme.text, font_size)
             .draw(
                 unit_text,
                 glyphs,
                 &DrawState::default(),
-                transform.trans(
-                    (self.width - text_width) / 2.0,
-                    (self.height + font_size as f64 * 0.75) / 2.0,
-                ),
+                transform.trans((self.width - text_width) / 2.0, label_y),
                 g2d,
             )
             .unwrap();
+
+        if let Some(description) = description {
+            // shrink a long description to the item width
+            let mut description_size = (font_size / 2).max(1);
+            let width = glyphs.width(description_size, description).unwrap();
+            if width > self.width * 0.95 {
+                description_size = ((description_size as f64 * self.width * 0.95 / width) as FontSize).max(8);
+            }
+            let description_width = glyphs.width(description_size, description).unwrap();
+            text::Text::new_color(theme.text, description_size)
+                .draw(
+                    description,
+                    glyphs,
+                    &DrawState::default(),
+                    transform.trans(
+                        (self.width - description_width) / 2.0,
+                        label_y + description_size as f64 * 1.6,
+                    ),
+                    g2d,
+                )
+                .unwrap();
+        }
     }
 
     fn is_hovered(&self, x: f64, y: f64) -> bool {
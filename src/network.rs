@@ -0,0 +1,263 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use crate::algorithms::graph::Graph;
+
+//Weighted directed graph loaded from a file, e.g. a road network
+pub struct Network {
+    // node ids as written in the files
+    pub names: Vec<String>,
+    // node index by its id
+    nodes: HashMap<String, usize>,
+    pub positions: Vec<[f64; 2]>,
+    pub edges: Vec<Vec<(usize, i32)>>,
+    pub start: usize,
+    pub goal: usize,
+    // lowest edge cost per unit of straight distance, none when nodes have no coordinates
+    cost_per_distance: Option<f64>,
+}
+
+impl Network {
+    fn new(
+        names: Vec<String>,
+        nodes: HashMap<String, usize>,
+        edges: Vec<Vec<(usize, i32)>>,
+    ) -> Result<Network, String> {
+        if names.is_empty() {
+            return Err("graph has no nodes".to_string());
+        }
+        let mut network = Network {
+            goal: names.len() - 1,
+            names,
+            nodes,
+            positions: Vec::new(),
+            edges,
+            start: 0,
+            cost_per_distance: None,
+        };
+        network.layout_on_circle();
+        Ok(network)
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.edges.iter().map(Vec::len).sum()
+    }
+
+    pub fn node(&self, name: &str) -> Result<usize, String> {
+        self.nodes.get(name).copied().ok_or_else(|| format!("unknown node {}", name))
+    }
+
+    //Start and goal by node ids, the first and the last node when not given
+    pub fn set_endpoints(&mut self, from: Option<&str>, to: Option<&str>) -> Result<(), String> {
+        if let Some(name) = from {
+            self.start = self.node(name)?;
+        }
+        if let Some(name) = to {
+            self.goal = self.node(name)?;
+        }
+        Ok(())
+    }
+
+    //Nodes without coordinates are placed evenly on a circle
+    fn layout_on_circle(&mut self) {
+        let step = std::f64::consts::TAU / self.names.len() as f64;
+        self.positions = (0..self.names.len())
+            .map(|n| [(step * n as f64).cos(), (step * n as f64).sin()])
+            .collect();
+        self.cost_per_distance = None;
+    }
+
+    //Straight line heuristic is admissible when scaled by the cheapest cost per distance of all edges
+    fn set_positions(&mut self, positions: Vec<[f64; 2]>) {
+        let mut cost_per_distance = f64::INFINITY;
+        for (from, edges) in self.edges.iter().enumerate() {
+            for &(to, cost) in edges {
+                let distance = distance(positions[from], positions[to]);
+                if distance > 0.0 {
+                    cost_per_distance = cost_per_distance.min(cost as f64 / distance);
+                }
+            }
+        }
+        self.positions = positions;
+        self.cost_per_distance = cost_per_distance.is_finite().then_some(cost_per_distance);
+    }
+}
+
+impl Graph for Network {
+    type Node = usize;
+
+    fn start(&self) -> usize {
+        self.start
+    }
+
+    fn goal(&self) -> usize {
+        self.goal
+    }
+
+    fn neighbors(&self, node: usize) -> Vec<(usize, i32)> {
+        self.edges[node].clone()
+    }
+
+    fn heuristic(&self, node: usize) -> Option<i32> {
+        let cost_per_distance = self.cost_per_distance?;
+        Some((cost_per_distance * distance(self.positions[node], self.positions[self.goal])).floor() as i32)
+    }
+}
+
+fn distance(a: [f64; 2], b: [f64; 2]) -> f64 {
+    (a[0] - b[0]).hypot(a[1] - b[1])
+}
+
+//Graph file by its extension: DIMACS .gr or CSV edge list. Coordinates are a DIMACS .co file
+//for the first one and a CSV of `id,x,y` for the second one
+pub fn load_network(path: &Path, coordinates: Option<&Path>) -> Result<Network, String> {
+    let read = |path: &Path| fs::read_to_string(path).map_err(|error| format!("{}: {}", path.display(), error));
+    let graph = read(path)?;
+    let coordinates = coordinates.map(read).transpose()?;
+    let with_path = |error: String| format!("{}: {}", path.display(), error);
+    match path.extension().and_then(|extension| extension.to_str()) {
+        Some("gr") => parse_dimacs(&graph, coordinates.as_deref()).map_err(with_path),
+        Some("csv") => parse_edge_list(&graph, coordinates.as_deref()).map_err(with_path),
+        _ => Err(with_path("unknown graph format, expected .gr or .csv".to_string())),
+    }
+}
+
+//DIMACS shortest path format: `p sp <nodes> <arcs>` and `a <from> <to> <cost>` lines,
+//coordinates are `v <id> <x> <y>` lines. Nodes are numbered from 1
+pub fn parse_dimacs(graph: &str, coordinates: Option<&str>) -> Result<Network, String> {
+    let mut edges: Option<Vec<Vec<(usize, i32)>>> = None;
+    let mut expected_arcs = 0;
+    let mut arcs = 0;
+    for (number, line) in graph.lines().enumerate() {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        let error = |message: &str| format!("line {}: {}", number + 1, message);
+        match fields.as_slice() {
+            [] | ["c", ..] => {}
+            ["p", "sp", nodes, arcs] => {
+                let nodes: usize = nodes.parse().map_err(|_| error("invalid number of nodes"))?;
+                expected_arcs = arcs.parse().map_err(|_| error("invalid number of arcs"))?;
+                edges = Some(vec![Vec::new(); nodes]);
+            }
+            ["a", from, to, cost] => {
+                let edges = edges.as_mut().ok_or_else(|| error("arc before the problem line"))?;
+                let from = dimacs_node(from, edges.len()).ok_or_else(|| error("invalid arc start"))?;
+                let to = dimacs_node(to, edges.len()).ok_or_else(|| error("invalid arc end"))?;
+                let cost = parse_cost(cost).map_err(|message| error(&message))?;
+                edges[from].push((to, cost));
+                arcs += 1;
+            }
+            _ => return Err(error("expected a comment, problem or arc line")),
+        }
+    }
+    let edges = edges.ok_or("no problem line `p sp <nodes> <arcs>`")?;
+    if arcs != expected_arcs {
+        return Err(format!("{} arcs are declared, {} are given", expected_arcs, arcs));
+    }
+
+    let names: Vec<String> = (1..=edges.len()).map(|id| id.to_string()).collect();
+    let nodes = names.iter().enumerate().map(|(index, name)| (name.clone(), index)).collect();
+    let mut network = Network::new(names, nodes, edges)?;
+    if let Some(coordinates) = coordinates {
+        let mut positions = vec![None; network.names.len()];
+        for (number, line) in coordinates.lines().enumerate() {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let error = |message: &str| format!("coordinates line {}: {}", number + 1, message);
+            match fields.as_slice() {
+                [] | ["c", ..] | ["p", ..] => {}
+                ["v", id, x, y] => {
+                    let node = dimacs_node(id, positions.len()).ok_or_else(|| error("invalid node"))?;
+                    positions[node] = Some(parse_point(x, y).map_err(|message| error(&message))?);
+                }
+                _ => return Err(error("expected a comment, problem or node line")),
+            }
+        }
+        network.set_positions(complete_positions(&network.names, positions)?);
+    }
+    Ok(network)
+}
+
+fn dimacs_node(id: &str, nodes: usize) -> Option<usize> {
+    id.parse::<usize>().ok().filter(|id| (1..=nodes).contains(id)).map(|id| id - 1)
+}
+
+//CSV edge list of `from,to,cost` rows, edges are directed. Node ids are any text,
+//a header row and lines starting with # are skipped
+pub fn parse_edge_list(graph: &str, coordinates: Option<&str>) -> Result<Network, String> {
+    let mut names: Vec<String> = Vec::new();
+    let mut nodes: HashMap<String, usize> = HashMap::new();
+    let mut edges: Vec<Vec<(usize, i32)>> = Vec::new();
+    let mut node = |name: &str, edges: &mut Vec<Vec<(usize, i32)>>| {
+        *nodes.entry(name.to_string()).or_insert_with(|| {
+            names.push(name.to_string());
+            edges.push(Vec::new());
+            names.len() - 1
+        })
+    };
+
+    // the header is the first row that is not empty or a comment
+    for (row, (number, fields)) in csv_rows(graph).enumerate() {
+        let error = |message: &str| format!("line {}: {}", number, message);
+        let [from, to, cost] = fields.as_slice() else {
+            return Err(error("expected `from,to,cost`"));
+        };
+        let cost = match parse_cost(cost) {
+            Ok(cost) => cost,
+            Err(_) if row == 0 => continue,
+            Err(message) => return Err(error(&message)),
+        };
+        let from = node(from, &mut edges);
+        let to = node(to, &mut edges);
+        edges[from].push((to, cost));
+    }
+
+    let mut network = Network::new(names, nodes, edges)?;
+    if let Some(coordinates) = coordinates {
+        let mut positions = vec![None; network.names.len()];
+        for (row, (number, fields)) in csv_rows(coordinates).enumerate() {
+            let error = |message: &str| format!("coordinates line {}: {}", number, message);
+            let [id, x, y] = fields.as_slice() else {
+                return Err(error("expected `id,x,y`"));
+            };
+            match parse_point(x, y) {
+                Ok(point) => positions[network.node(id).map_err(|message| error(&message))?] = Some(point),
+                Err(_) if row == 0 => continue,
+                Err(message) => return Err(error(&message)),
+            }
+        }
+        network.set_positions(complete_positions(&network.names, positions)?);
+    }
+    Ok(network)
+}
+
+//Not empty and not commented rows with their line numbers
+fn csv_rows(content: &str) -> impl Iterator<Item = (usize, Vec<&str>)> {
+    content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty() && !line.trim_start().starts_with('#'))
+        .map(|(number, line)| (number + 1, line.split(',').map(str::trim).collect()))
+}
+
+fn parse_cost(cost: &str) -> Result<i32, String> {
+    match cost.parse::<i32>() {
+        Ok(cost) if cost >= 0 => Ok(cost),
+        Ok(_) => Err("edge cost is negative".to_string()),
+        Err(_) => Err(format!("invalid edge cost {}", cost)),
+    }
+}
+
+fn parse_point(x: &str, y: &str) -> Result<[f64; 2], String> {
+    match (x.parse::<f64>(), y.parse::<f64>()) {
+        (Ok(x), Ok(y)) if x.is_finite() && y.is_finite() => Ok([x, y]),
+        _ => Err(format!("invalid coordinates {}, {}", x, y)),
+    }
+}
+
+fn complete_positions(names: &[String], positions: Vec<Option<[f64; 2]>>) -> Result<Vec<[f64; 2]>, String> {
+    positions
+        .into_iter()
+        .zip(names)
+        .map(|(position, name)| position.ok_or_else(|| format!("node {} has no coordinates", name)))
+        .collect()
+}
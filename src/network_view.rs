@@ -0,0 +1,311 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+
+use piston_window::{clear, ellipse, rectangle, text, CharacterCache, Context, G2d, Glyphs, Line, MouseButton, Transformed};
+
+use crate::algorithms::graph::SearchObserver;
+use crate::algorithms::AlgorithmType;
+use crate::camera::Camera;
+use crate::cell::CellState;
+use crate::colors::Theme;
+use crate::network::Network;
+use crate::run::{pause, SearchRun};
+use crate::settings::{Settings, Vec2f};
+use crate::state::SearchStats;
+
+// longer side of the network drawing in field pixels
+const NETWORK_SIZE: f64 = 1000.0;
+// node radius in window pixels, it does not change with zoom
+const NODE_RADIUS: f64 = 3.0;
+
+//Search progress on a network, nodes reuse the cell states of the grid
+pub struct NetworkSearch {
+    pub node_states: Vec<CellState>,
+    pub stats: SearchStats,
+    pub path: Vec<usize>,
+}
+
+struct NetworkObserver {
+    search: Arc<Mutex<NetworkSearch>>,
+    should_stop: Arc<AtomicBool>,
+    // microseconds between search steps
+    delay: f64,
+}
+
+impl NetworkObserver {
+    fn search(&self) -> MutexGuard<'_, NetworkSearch> {
+        self.search.lock().unwrap()
+    }
+
+    //Search states never replace start and goal nodes
+    fn mark_node(&self, node: usize, state: CellState) {
+        let mut search = self.search();
+        if !matches!(search.node_states[node], CellState::Start | CellState::End) {
+            search.node_states[node] = state;
+        }
+    }
+}
+
+impl SearchObserver<usize> for NetworkObserver {
+    fn should_stop(&self) -> bool {
+        self.should_stop.load(Ordering::SeqCst)
+    }
+
+    fn expand(&mut self, node: usize, frontier: usize, g: Option<i32>, f: Option<i32>) {
+        if self.delay > 0.0 {
+            pause(Duration::from_micros(self.delay as u64), &self.should_stop);
+        }
+        self.search().stats.expand(frontier, g, f);
+        self.mark_node(node, CellState::Current);
+    }
+
+    fn discover(&mut self, node: usize, parent: Option<usize>, _g: Option<i32>, _h: Option<i32>, reopened: bool) {
+        if parent.is_some() {
+            self.mark_node(node, if reopened { CellState::Reopened } else { CellState::Frontier });
+        }
+    }
+
+    fn close(&mut self, node: usize) {
+        self.mark_node(node, CellState::Closed);
+    }
+
+    fn found(&mut self, path: &[usize], cost: i32) {
+        for &node in path {
+            self.mark_node(node, CellState::Chosen);
+        }
+        let mut search = self.search();
+        search.stats.path_length = Some(path.len());
+        search.stats.path_cost = Some(cost);
+        search.path = path.to_vec();
+    }
+}
+
+//Network drawn with nodes at their coordinates and edges as lines, searched by one algorithm
+pub struct NetworkView {
+    network: Arc<Network>,
+    // node positions in field pixels
+    points: Vec<[f64; 2]>,
+    field_size: Vec2f,
+    search: Arc<Mutex<NetworkSearch>>,
+    algorithm_type: AlgorithmType,
+    settings: Settings,
+    camera: Camera,
+    should_stop: Arc<AtomicBool>,
+    run: Option<SearchRun>,
+    mouse_coordinates: Vec2f,
+    // last mouse position while the network is dragged
+    drag_from: Option<Vec2f>,
+}
+
+impl NetworkView {
+    pub fn new(settings: Settings, algorithm_type: AlgorithmType, network: Network) -> NetworkView {
+        let (points, field_size) = fit_points(&network.positions);
+        let mut node_states = vec![CellState::Empty; network.names.len()];
+        node_states[network.start] = CellState::Start;
+        node_states[network.goal] = CellState::End;
+        NetworkView {
+            network: Arc::new(network),
+            points,
+            field_size,
+            search: Arc::new(Mutex::new(NetworkSearch {
+                node_states,
+                stats: SearchStats::default(),
+                path: Vec::new(),
+            })),
+            algorithm_type,
+            settings,
+            camera: Camera::new(field_size, settings.window_size),
+            should_stop: Arc::new(AtomicBool::new(false)),
+            run: None,
+            mouse_coordinates: Vec2f { raw_x: 0.0, raw_y: 0.0 },
+            drag_from: None,
+        }
+    }
+
+    pub fn start(&mut self) {
+        let network = Arc::clone(&self.network);
+        let algorithm_type = self.algorithm_type;
+        let mut observer = NetworkObserver {
+            search: Arc::clone(&self.search),
+            should_stop: Arc::clone(&self.should_stop),
+            delay: self.settings.step_delay * 1000.0 / self.settings.speed,
+        };
+        let run = SearchRun::spawn("network", algorithm_type.name(), Arc::clone(&self.should_stop), move || {
+            observer.search().stats.start();
+            algorithm_type.search(network.as_ref(), &mut observer);
+            observer.search().stats.finish();
+        });
+        self.run = Some(run);
+    }
+
+    //Search thread, taken to be stopped
+    pub fn take_run(&mut self) -> Option<SearchRun> {
+        self.run.take()
+    }
+
+    pub fn render(&mut self, context: Context, g2d: &mut G2d, glyphs: &mut Glyphs) {
+        let theme = self.settings.theme;
+        clear(theme.empty_field, g2d);
+        let field_context = self.camera.apply(context);
+        // lines and nodes keep their window size at any zoom
+        let pixel = 1.0 / self.camera.zoom;
+        let [view_x, view_y, view_width, view_height] = self.camera.viewport(self.settings.window_size);
+        let is_visible = |[x, y]: [f64; 2]| {
+            x >= view_x && x <= view_x + view_width && y >= view_y && y <= view_y + view_height
+        };
+
+        let edge_line = Line::new(theme.grid_line, pixel * 0.5);
+        for (from, edges) in self.network.edges.iter().enumerate() {
+            for &(to, _) in edges {
+                let (a, b) = (self.points[from], self.points[to]);
+                if is_visible(a) || is_visible(b) {
+                    edge_line.draw([a[0], a[1], b[0], b[1]], &field_context.draw_state, field_context.transform, g2d);
+                }
+            }
+        }
+
+        let search = self.search.lock().unwrap();
+        let path_line = Line::new(theme.path_line, pixel * 2.0);
+        for step in search.path.windows(2) {
+            let (a, b) = (self.points[step[0]], self.points[step[1]]);
+            path_line.draw([a[0], a[1], b[0], b[1]], &field_context.draw_state, field_context.transform, g2d);
+        }
+
+        for (node, &point) in self.points.iter().enumerate() {
+            if !is_visible(point) {
+                continue;
+            }
+            let (color, radius) = match search.node_states[node] {
+                CellState::Start => (theme.start_cell, NODE_RADIUS * 2.0),
+                CellState::End => (theme.end_cell, NODE_RADIUS * 2.0),
+                CellState::Frontier => (theme.frontier_cell, NODE_RADIUS),
+                CellState::Closed => (theme.closed_cell, NODE_RADIUS),
+                CellState::Current => (theme.current_cell, NODE_RADIUS * 1.5),
+                CellState::Reopened => (theme.reopened_cell, NODE_RADIUS),
+                CellState::Chosen => (theme.chosen_cell, NODE_RADIUS * 1.5),
+                CellState::Empty | CellState::Blocked => (theme.text, NODE_RADIUS * 0.7),
+            };
+            let radius = radius * pixel;
+            ellipse(
+                color,
+                [point[0] - radius, point[1] - radius, radius * 2.0, radius * 2.0],
+                field_context.transform,
+                g2d,
+            );
+        }
+
+        if self.settings.show_hud {
+            self.render_hud(&search, context, g2d, glyphs);
+        }
+    }
+
+    //Overlay with the network size and the search statistics in the top left corner
+    fn render_hud(&self, search: &NetworkSearch, context: Context, g2d: &mut G2d, glyphs: &mut Glyphs) {
+        let stats = &search.stats;
+        let mut lines = vec![
+            self.algorithm_type.name().to_string(),
+            format!("nodes: {}  edges: {}", self.network.names.len(), self.network.edge_count()),
+            format!(
+                "from: {}  to: {}",
+                self.network.names[self.network.start], self.network.names[self.network.goal]
+            ),
+            format!("expanded: {}  frontier: {}", stats.expanded, stats.frontier),
+        ];
+        lines.push(format!("time: {:.2} s", stats.elapsed().as_secs_f64()));
+        lines.push(match (stats.path_length, stats.path_cost) {
+            (Some(length), Some(cost)) => format!("path: {} nodes, cost {}", length, cost),
+            _ if stats.is_finished() => "path: not found".to_string(),
+            _ => "path: searching".to_string(),
+        });
+
+        let font_size = self.settings.font_size;
+        let line_height = font_size as f64 * 1.4;
+        let padding = self.settings.cell_offset.raw_x;
+        let hud_width = lines
+            .iter()
+            .map(|line| glyphs.width(font_size, line).unwrap())
+            .fold(0.0, f64::max);
+        rectangle(
+            self.settings.theme.hud_background,
+            [0.0, 0.0, hud_width + padding * 2.0, line_height * lines.len() as f64 + padding],
+            context.transform,
+            g2d,
+        );
+        for (n, line) in lines.iter().enumerate() {
+            text::Text::new_color(self.settings.theme.text, font_size)
+                .draw(
+                    line,
+                    glyphs,
+                    &context.draw_state,
+                    context.transform.trans(padding, line_height * (n + 1) as f64),
+                    g2d,
+                )
+                .unwrap();
+        }
+    }
+
+    pub fn set_theme(&mut self, theme: Theme) {
+        self.settings.theme = theme;
+    }
+
+    pub fn set_show_hud(&mut self, show_hud: bool) {
+        self.settings.show_hud = show_hud;
+    }
+
+    pub fn set_window_size(&mut self, window_size: Vec2f) {
+        self.settings.window_size = window_size;
+    }
+
+    pub fn fit_to_window(&mut self) {
+        self.camera.fit(self.field_size, self.settings.window_size);
+    }
+
+    pub fn zoom(&mut self, steps: f64) {
+        self.camera.zoom_at(self.mouse_coordinates, steps);
+    }
+
+    pub fn on_mouse_click(&mut self, button: &MouseButton) {
+        if let &MouseButton::Left = button {
+            self.drag_from = Some(self.mouse_coordinates);
+        }
+    }
+
+    pub fn on_mouse_release(&mut self, button: &MouseButton) {
+        if let &MouseButton::Left = button {
+            self.drag_from = None;
+        }
+    }
+
+    pub fn on_mouse_move(&mut self, args: &[f64; 2]) {
+        self.mouse_coordinates.raw_x = args[0];
+        self.mouse_coordinates.raw_y = args[1];
+        if let Some(drag_from) = self.drag_from {
+            self.camera.pan(args[0] - drag_from.raw_x, args[1] - drag_from.raw_y);
+            self.drag_from = Some(self.mouse_coordinates);
+        }
+    }
+}
+
+//Scale node coordinates into field pixels with a margin, y grows up as on maps
+fn fit_points(positions: &[[f64; 2]]) -> (Vec<[f64; 2]>, Vec2f) {
+    let (mut min_x, mut min_y) = (f64::INFINITY, f64::INFINITY);
+    let (mut max_x, mut max_y) = (f64::NEG_INFINITY, f64::NEG_INFINITY);
+    for &[x, y] in positions {
+        (min_x, max_x) = (min_x.min(x), max_x.max(x));
+        (min_y, max_y) = (min_y.min(y), max_y.max(y));
+    }
+    // a single node or nodes on one line still get an area
+    let span = (max_x - min_x).max(max_y - min_y).max(f64::EPSILON);
+    let scale = NETWORK_SIZE / span;
+    let margin = NETWORK_SIZE * 0.05;
+    let points = positions
+        .iter()
+        .map(|&[x, y]| [margin + (x - min_x) * scale, margin + (max_y - y) * scale])
+        .collect();
+    let field_size = Vec2f {
+        raw_x: (max_x - min_x) * scale + margin * 2.0,
+        raw_y: (max_y - min_y) * scale + margin * 2.0,
+    };
+    (points, field_size)
+}
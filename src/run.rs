@@ -0,0 +1,143 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+// a cancelled search notices the stop flag within this time while it waits between steps
+const STOP_CHECK_INTERVAL: Duration = Duration::from_millis(5);
+
+//How a search run ended
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum RunStatus {
+    Finished,
+    Cancelled,
+    Panicked,
+    // the thread did not stop in time and is still running
+    TimedOut,
+}
+
+impl RunStatus {
+    pub fn name(self) -> &'static str {
+        match self {
+            RunStatus::Finished => "finished",
+            RunStatus::Cancelled => "cancelled",
+            RunStatus::Panicked => "failed",
+            RunStatus::TimedOut => "still stopping",
+        }
+    }
+}
+
+//Search thread with the flag that stops it
+pub struct SearchRun {
+    name: String,
+    should_stop: Arc<AtomicBool>,
+    handle: JoinHandle<()>,
+}
+
+impl SearchRun {
+    //Name is shown in reports, the thread is named by its kind
+    pub fn spawn(
+        thread_name: &str,
+        name: &str,
+        should_stop: Arc<AtomicBool>,
+        search: impl FnOnce() + Send + 'static,
+    ) -> SearchRun {
+        let handle = thread::Builder::new()
+            .name(thread_name.to_string())
+            .spawn(search)
+            .unwrap();
+        SearchRun { name: name.to_string(), should_stop, handle }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.handle.is_finished()
+    }
+
+    pub fn cancel(&self) {
+        self.should_stop.store(true, Ordering::SeqCst);
+    }
+
+    //Waits for the thread until the deadline, the run is given back when it is still alive
+    fn join_until(self, deadline: Instant) -> Result<bool, SearchRun> {
+        while !self.handle.is_finished() {
+            if Instant::now() >= deadline {
+                return Err(self);
+            }
+            thread::sleep(Duration::from_millis(1));
+        }
+        Ok(self.handle.join().is_ok())
+    }
+}
+
+//Stops search runs and keeps the ones that did not stop in time, so no thread is lost
+pub struct RunManager {
+    timeout: Duration,
+    // cancelled runs still alive after the timeout
+    lingering: Vec<SearchRun>,
+    // how the last stopped runs ended
+    pub last_report: Option<String>,
+}
+
+impl RunManager {
+    pub fn new(timeout: Duration) -> RunManager {
+        RunManager { timeout, lingering: Vec::new(), last_report: None }
+    }
+
+    //Cancel all runs at once, then wait for them together up to the timeout
+    pub fn stop(&mut self, runs: Vec<SearchRun>) -> Vec<(String, RunStatus)> {
+        if runs.is_empty() {
+            return Vec::new();
+        }
+        let finished: Vec<bool> = runs.iter().map(SearchRun::is_finished).collect();
+        for run in runs.iter() {
+            run.cancel();
+        }
+        let deadline = Instant::now() + self.timeout;
+        let mut statuses = Vec::new();
+        for (run, finished) in runs.into_iter().zip(finished) {
+            let name = run.name.clone();
+            let status = match run.join_until(deadline) {
+                Ok(true) if finished => RunStatus::Finished,
+                Ok(true) => RunStatus::Cancelled,
+                Ok(false) => RunStatus::Panicked,
+                Err(run) => {
+                    self.lingering.push(run);
+                    RunStatus::TimedOut
+                }
+            };
+            statuses.push((name, status));
+        }
+        self.last_report = Some(
+            statuses
+                .iter()
+                .map(|(name, status)| format!("{} {}", name, status.name()))
+                .collect::<Vec<String>>()
+                .join(", "),
+        );
+        statuses
+    }
+
+    //Number of cancelled threads still alive, the ones that have stopped since are joined
+    pub fn lingering(&mut self) -> usize {
+        let (stopped, alive): (Vec<SearchRun>, Vec<SearchRun>) =
+            self.lingering.drain(..).partition(SearchRun::is_finished);
+        for run in stopped {
+            let _ = run.handle.join();
+        }
+        self.lingering = alive;
+        self.lingering.len()
+    }
+}
+
+//Sleep between search steps, cut short when the search is cancelled
+pub fn pause(delay: Duration, should_stop: &AtomicBool) {
+    let deadline = Instant::now() + delay;
+    loop {
+        let now = Instant::now();
+        if now >= deadline || should_stop.load(Ordering::SeqCst) {
+            return;
+        }
+        thread::sleep(STOP_CHECK_INTERVAL.min(deadline - now));
+    }
+}
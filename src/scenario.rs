@@ -0,0 +1,117 @@
+use crate::cell::{CellCoordinates, CellState};
+use crate::config::MAX_CELLS_NUMBER;
+use crate::field::Field;
+
+// Map legend:
+//  '#' - blocked cell
+//  '.' - cell with cost 1
+//  ',' - cell with cost 5
+//  '~' - cell with cost 15 (swamp)
+//  'S' - start cell
+//  'E' - end cell
+pub struct Scenario {
+    pub name: &'static str,
+    map: &'static str,
+}
+
+pub const SCENARIOS: [Scenario; 7] = [
+    Scenario {
+        name: "Open field",
+        map: include_str!("../assets/maps/open_field.txt"),
+    },
+    Scenario {
+        name: "Single wall",
+        map: include_str!("../assets/maps/single_wall.txt"),
+    },
+    Scenario {
+        name: "U-shaped trap",
+        map: include_str!("../assets/maps/u_trap.txt"),
+    },
+    Scenario {
+        name: "Maze",
+        map: include_str!("../assets/maps/maze.txt"),
+    },
+    Scenario {
+        name: "Weighted swamp",
+        map: include_str!("../assets/maps/weighted_swamp.txt"),
+    },
+    Scenario {
+        name: "Narrow corridor",
+        map: include_str!("../assets/maps/narrow_corridor.txt"),
+    },
+    Scenario {
+        name: "Spiral",
+        map: include_str!("../assets/maps/spiral.txt"),
+    },
+];
+
+impl Scenario {
+    pub fn build(&self) -> Field {
+        parse_map(self.map).expect("Bundled map is invalid!")
+    }
+}
+
+pub fn parse_map(map: &str) -> Result<Field, String> {
+    let rows: Vec<&str> = map.lines().filter(|line| !line.trim().is_empty()).collect();
+    let columns = rows.first().map_or(0, |row| row.trim_end().chars().count());
+    if columns == 0 {
+        return Err("map is empty".to_string());
+    }
+    // the same bounds as generated maps
+    let cells_numbers = 2..=MAX_CELLS_NUMBER as usize;
+    if !cells_numbers.contains(&columns) || !cells_numbers.contains(&rows.len()) {
+        return Err(format!(
+            "map is {}x{} cells, columns and rows must be in [2; {}]",
+            columns,
+            rows.len(),
+            MAX_CELLS_NUMBER
+        ));
+    }
+
+    let mut field = Field::new(columns as u16, rows.len() as u16);
+    let mut start = None;
+    let mut end = None;
+    for (y, row) in rows.iter().enumerate() {
+        let row: Vec<char> = row.trim_end().chars().collect();
+        if row.len() != columns {
+            return Err(format!(
+                "row {} has {} cells, first row has {}",
+                y + 1,
+                row.len(),
+                columns
+            ));
+        }
+        for (x, symbol) in row.into_iter().enumerate() {
+            let coordinates = CellCoordinates {
+                x: x as u16,
+                y: y as u16,
+            };
+            let tile = field.get_cell(coordinates.x, coordinates.y);
+            let mut cell = tile.get();
+            match symbol {
+                '#' => cell.set_state(CellState::Blocked),
+                '.' => cell.cost = 1,
+                ',' => cell.cost = 5,
+                '~' => cell.cost = 15,
+                'S' => {
+                    cell.cost = 1;
+                    if start.replace(coordinates).is_some() {
+                        return Err(format!("second start cell 'S' at {}:{}", x, y));
+                    }
+                }
+                'E' => {
+                    cell.cost = 1;
+                    if end.replace(coordinates).is_some() {
+                        return Err(format!("second end cell 'E' at {}:{}", x, y));
+                    }
+                }
+                _ => return Err(format!("unknown symbol '{}' at {}:{}", symbol, x, y)),
+            }
+        }
+    }
+
+    field.start = start.ok_or("map has no start cell 'S'")?;
+    field.end = end.ok_or("map has no end cell 'E'")?;
+    field.mark_endpoints();
+    Ok(field)
+}
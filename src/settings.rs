@@ -1,3 +1,10 @@
+use crate::algorithms::Heuristic;
+use crate::colors::{Theme, LIGHT_THEME};
+use crate::field::Neighborhood;
+
+// starting window size limits, the window can be resized later
+const MIN_WINDOW_SIZE: Vec2f = Vec2f { raw_x: 600.0, raw_y: 600.0 };
+const MAX_WINDOW_SIZE: Vec2f = Vec2f { raw_x: 1280.0, raw_y: 900.0 };
 
 #[derive(Copy, Clone)]
 pub struct Vec2f {
@@ -5,23 +12,79 @@ pub struct Vec2f {
     pub raw_y: f64,
 }
 
+//Value drawn inside each cell
+#[derive(Copy, Clone, PartialEq)]
+pub enum CellLabel {
+    Cost,
+    G,
+    H,
+    F,
+    Order,
+}
+
+impl CellLabel {
+    pub fn next(self) -> CellLabel {
+        match self {
+            CellLabel::Cost => CellLabel::G,
+            CellLabel::G => CellLabel::H,
+            CellLabel::H => CellLabel::F,
+            CellLabel::F => CellLabel::Order,
+            CellLabel::Order => CellLabel::Cost,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            CellLabel::Cost => "cost",
+            CellLabel::G => "g",
+            CellLabel::H => "h",
+            CellLabel::F => "f = g + h",
+            CellLabel::Order => "expansion order",
+        }
+    }
+}
+
 #[derive(Copy, Clone)]
 pub struct Settings {
     pub window_size: Vec2f,
     pub cell_size: Vec2f,
-    pub cells_number: u16,
+    pub columns: u16,
+    pub rows: u16,
     pub cell_offset: Vec2f,
     pub font_size: u32,
+    pub menu_font_size: u32,
+    pub terrain: bool,
+    pub cost_gradient: bool,
+    pub show_hud: bool,
+    pub cell_label: CellLabel,
+    pub show_parents: bool,
+    // overview of the whole field, shown when the camera does not see all of it
+    pub show_minimap: bool,
+    pub theme: Theme,
+    pub seed: u64,
+    pub ensure_solvable: bool,
+    pub scenario: Option<usize>,
+    // share of blocked cells in generated maps
+    pub wall_density: f64,
+    pub min_cost: i16,
+    pub max_cost: i16,
+    // milliseconds between search steps, divided by speed
+    pub step_delay: f64,
+    pub speed: f64,
+    pub heuristic: Heuristic,
+    pub neighborhood: Neighborhood,
 }
 
 impl Settings {
-    pub fn new(cell_size: u16, cells_number: u16) -> Settings {
+    pub fn new(cell_size: u16, columns: u16, rows: u16) -> Settings {
         let font_size = cell_size as f64 * 0.65;
         Settings {
             font_size: font_size as u32,
+            menu_font_size: 35,
+            // big fields do not fit the screen, the camera zooms them out
             window_size: Vec2f {
-                raw_x: (cell_size * cells_number) as f64,
-                raw_y: (cell_size * cells_number) as f64,
+                raw_x: (cell_size as f64 * columns as f64).clamp(MIN_WINDOW_SIZE.raw_x, MAX_WINDOW_SIZE.raw_x),
+                raw_y: (cell_size as f64 * rows as f64).clamp(MIN_WINDOW_SIZE.raw_y, MAX_WINDOW_SIZE.raw_y),
             },
             cell_size: Vec2f {
                 raw_x: cell_size as f64,
@@ -31,7 +94,25 @@ impl Settings {
                 raw_x: cell_size as f64 / 2.0 - (font_size / 2.0),
                 raw_y: cell_size as f64 / 2.0 + (font_size / 2.5),
             },
-            cells_number,
+            columns,
+            rows,
+            terrain: true,
+            cost_gradient: false,
+            show_hud: true,
+            cell_label: CellLabel::Cost,
+            show_parents: false,
+            show_minimap: true,
+            theme: LIGHT_THEME,
+            seed: 0,
+            ensure_solvable: false,
+            scenario: None,
+            wall_density: 0.25,
+            min_cost: 0,
+            max_cost: 19,
+            step_delay: 25.0,
+            speed: 1.0,
+            heuristic: Heuristic::Manhattan,
+            neighborhood: Neighborhood::Four,
         }
     }
 }
@@ -0,0 +1,294 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use clap::ValueEnum;
+use piston_window::types::{Color, FontSize};
+use piston_window::{rectangle, text, CharacterCache, Context, DrawState, G2d, Glyphs, Transformed};
+
+use crate::config::{self, MAX_CELLS_NUMBER, MAX_CELL_COST};
+use crate::settings::{Settings, Vec2f};
+
+// columns and rows change by this many cells a step
+const CELLS_STEP: i32 = 5;
+// wall density changes in twentieths
+const DENSITY_STEPS: f64 = 20.0;
+// speed is doubled or halved in these limits
+const MIN_SPEED: f64 = 1.0 / 64.0;
+const MAX_SPEED: f64 = 64.0;
+
+//Values changed on the settings page, in the page order
+#[derive(Copy, Clone, PartialEq)]
+pub enum Setting {
+    Columns,
+    Rows,
+    WallDensity,
+    MinCost,
+    MaxCost,
+    Seed,
+    Neighborhood,
+    Heuristic,
+    Speed,
+    Theme,
+}
+
+impl Setting {
+    pub const ALL: [Setting; 10] = [
+        Setting::Columns,
+        Setting::Rows,
+        Setting::WallDensity,
+        Setting::MinCost,
+        Setting::MaxCost,
+        Setting::Seed,
+        Setting::Neighborhood,
+        Setting::Heuristic,
+        Setting::Speed,
+        Setting::Theme,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Setting::Columns => "Columns",
+            Setting::Rows => "Rows",
+            Setting::WallDensity => "Wall density",
+            Setting::MinCost => "Min cost",
+            Setting::MaxCost => "Max cost",
+            Setting::Seed => "Seed",
+            Setting::Neighborhood => "Neighbors",
+            Setting::Heuristic => "Heuristic",
+            Setting::Speed => "Speed",
+            Setting::Theme => "Theme",
+        }
+    }
+
+    pub fn value(self, settings: &Settings) -> String {
+        match self {
+            Setting::Columns => settings.columns.to_string(),
+            Setting::Rows => settings.rows.to_string(),
+            Setting::WallDensity => format!("{:.2}", settings.wall_density),
+            Setting::MinCost => settings.min_cost.to_string(),
+            Setting::MaxCost => settings.max_cost.to_string(),
+            Setting::Seed => settings.seed.to_string(),
+            Setting::Neighborhood => settings.neighborhood.name().to_string(),
+            Setting::Heuristic if !settings.heuristic.is_admissible(settings.neighborhood) => {
+                format!("{} (overestimates)", settings.heuristic.name())
+            }
+            Setting::Heuristic => settings.heuristic.name().to_string(),
+            Setting::Speed => format!("x{}", settings.speed),
+            Setting::Theme => settings.theme.kind.name().to_string(),
+        }
+    }
+
+    //Step the value up or down, it stays a valid map parameter
+    pub fn change(self, settings: &mut Settings, step: i32, theme_colors: &HashMap<String, Color>) {
+        let step_cells = |cells: u16| (cells as i32 + step * CELLS_STEP).clamp(2, MAX_CELLS_NUMBER as i32) as u16;
+        match self {
+            Setting::Columns => settings.columns = step_cells(settings.columns),
+            Setting::Rows => settings.rows = step_cells(settings.rows),
+            Setting::WallDensity => {
+                let steps = (settings.wall_density * DENSITY_STEPS).round() + step as f64;
+                settings.wall_density = steps.clamp(0.0, DENSITY_STEPS - 1.0) / DENSITY_STEPS;
+            }
+            Setting::MinCost => {
+                settings.min_cost = (settings.min_cost as i32 + step).clamp(0, settings.max_cost as i32) as i16;
+            }
+            Setting::MaxCost => {
+                settings.max_cost = (settings.max_cost as i32 + step).clamp(settings.min_cost as i32, MAX_CELL_COST as i32) as i16;
+            }
+            Setting::Seed => settings.seed = settings.seed.wrapping_add_signed(step as i64),
+            Setting::Neighborhood => settings.neighborhood = cycle(settings.neighborhood, step),
+            Setting::Heuristic => settings.heuristic = cycle(settings.heuristic, step),
+            Setting::Speed => settings.speed = (settings.speed * 2f64.powi(step)).clamp(MIN_SPEED, MAX_SPEED),
+            Setting::Theme => {
+                settings.theme = cycle(settings.theme.kind, step)
+                    .theme()
+                    .with_overrides(theme_colors)
+                    .expect("Theme colors are validated on start!");
+            }
+        }
+    }
+
+    //Section, key and value of the setting in the config file
+    pub fn config_entry(self, settings: &Settings) -> Result<(&'static str, &'static str, toml_edit::Value), String> {
+        let entry = match self {
+            Setting::Columns => ("grid", "columns", toml_edit::Value::from(settings.columns as i64)),
+            Setting::Rows => ("grid", "rows", toml_edit::Value::from(settings.rows as i64)),
+            Setting::WallDensity => ("grid", "wall_density", toml_edit::Value::from(settings.wall_density)),
+            Setting::MinCost => ("grid", "min_cost", toml_edit::Value::from(settings.min_cost as i64)),
+            Setting::MaxCost => ("grid", "max_cost", toml_edit::Value::from(settings.max_cost as i64)),
+            Setting::Seed => {
+                // TOML integers are signed
+                let seed = i64::try_from(settings.seed)
+                    .map_err(|_| format!("seed {} is too big for the config file", settings.seed))?;
+                ("grid", "seed", toml_edit::Value::from(seed))
+            }
+            Setting::Neighborhood => ("grid", "neighborhood", value_name(settings.neighborhood)),
+            Setting::Heuristic => ("search", "heuristic", value_name(settings.heuristic)),
+            Setting::Speed => ("search", "speed", toml_edit::Value::from(settings.speed)),
+            Setting::Theme => ("theme", "base", value_name(settings.theme.kind)),
+        };
+        Ok(entry)
+    }
+}
+
+//Next or previous variant, wrapping around
+fn cycle<T: ValueEnum + PartialEq + Copy>(value: T, step: i32) -> T {
+    let variants = T::value_variants();
+    let index = variants.iter().position(|&variant| variant == value).unwrap_or(0) as i32;
+    variants[(index + step).rem_euclid(variants.len() as i32) as usize]
+}
+
+//Config files use the command line names
+fn value_name<T: ValueEnum>(value: T) -> toml_edit::Value {
+    let name = value.to_possible_value().expect("Every value has a name!");
+    toml_edit::Value::from(name.get_name())
+}
+
+//Page of the menu with a row of controls for every setting. Changes are applied to the next
+//algorithm run and written to the config file when the page is left, so they are kept after a restart
+pub struct SettingsPage {
+    config_path: PathBuf,
+    pub focus: usize,
+    // settings changed since the last save
+    changed: Vec<Setting>,
+    // result of the last save
+    pub message: Option<String>,
+    // layout of the last frame
+    top: f64,
+    row_height: f64,
+    window_size: Vec2f,
+}
+
+impl SettingsPage {
+    pub fn new(config_path: PathBuf) -> SettingsPage {
+        SettingsPage {
+            config_path,
+            focus: 0,
+            changed: Vec::new(),
+            message: None,
+            top: 0.0,
+            row_height: 0.0,
+            window_size: Vec2f { raw_x: 0.0, raw_y: 0.0 },
+        }
+    }
+
+    pub fn focused(&self) -> Setting {
+        Setting::ALL[self.focus]
+    }
+
+    pub fn move_focus(&mut self, step: isize) {
+        self.focus = self.focus.saturating_add_signed(step).min(Setting::ALL.len() - 1);
+    }
+
+    pub fn mark_changed(&mut self, setting: Setting) {
+        if !self.changed.contains(&setting) {
+            self.changed.push(setting);
+        }
+    }
+
+    //Write the changed settings to the config file at once, nothing is written without changes
+    pub fn save(&mut self, settings: &Settings) {
+        if self.changed.is_empty() {
+            return;
+        }
+        let saved = self.changed
+            .iter()
+            .map(|setting| setting.config_entry(settings))
+            .collect::<Result<Vec<_>, String>>()
+            .and_then(|values| config::save_values(&self.config_path, values));
+        self.message = Some(match saved {
+            Ok(()) => format!("Settings saved to {}", self.config_path.display()),
+            Err(error) => format!("Settings not saved: {}", error),
+        });
+        self.changed.clear();
+    }
+
+    //Row under the point and the step of its button: -1 and 1 for the arrows, 0 for the rest of the row
+    pub fn control_at(&self, x: f64, y: f64) -> Option<(usize, i32)> {
+        if y < self.top || self.row_height <= 0.0 {
+            return None;
+        }
+        let row = ((y - self.top) / self.row_height) as usize;
+        if row >= Setting::ALL.len() {
+            return None;
+        }
+        let (less, more) = self.buttons(row);
+        let is_inside = |[button_x, button_y, width, height]: [f64; 4]| {
+            x >= button_x && x <= button_x + width && y >= button_y && y <= button_y + height
+        };
+        let step = if is_inside(less) {
+            -1
+        } else if is_inside(more) {
+            1
+        } else {
+            0
+        };
+        Some((row, step))
+    }
+
+    //Rectangles of the buttons lowering and raising the value of a row
+    fn buttons(&self, row: usize) -> ([f64; 4], [f64; 4]) {
+        let size = self.row_height * 0.8;
+        let y = self.top + row as f64 * self.row_height + (self.row_height - size) / 2.0;
+        let less = [self.window_size.raw_x * 0.5, y, size, size];
+        let more = [self.window_size.raw_x * 0.85 - size, y, size, size];
+        (less, more)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn render(
+        &mut self,
+        settings: &Settings,
+        top: f64,
+        bottom: f64,
+        font_size: FontSize,
+        mouse: Vec2f,
+        context: Context,
+        g2d: &mut G2d,
+        glyphs: &mut Glyphs,
+    ) {
+        let theme = settings.theme;
+        self.top = top;
+        self.row_height = ((bottom - top) / Setting::ALL.len() as f64).min(font_size as f64 * 1.5);
+        self.window_size = settings.window_size;
+        let text_size = (self.row_height * 0.55) as FontSize;
+        let hovered = self.control_at(mouse.raw_x, mouse.raw_y);
+
+        for (row, setting) in Setting::ALL.iter().enumerate() {
+            let row_y = top + row as f64 * self.row_height;
+            let text_y = row_y + (self.row_height + text_size as f64 * 0.7) / 2.0;
+            let left = self.window_size.raw_x * 0.15;
+            let width = self.window_size.raw_x * 0.7;
+            if row == self.focus {
+                let frame = 2.0;
+                rectangle(theme.focus_frame, [left, row_y, width, self.row_height], context.transform, g2d);
+                rectangle(
+                    theme.menu_background,
+                    [left + frame, row_y + frame, width - frame * 2.0, self.row_height - frame * 2.0],
+                    context.transform,
+                    g2d,
+                );
+            }
+            draw_text(setting.name(), left + text_size as f64 / 2.0, text_y, text_size, theme.text, context, g2d, glyphs);
+
+            let (less, more) = self.buttons(row);
+            for (button, step, sign) in [(less, -1, "<"), (more, 1, ">")] {
+                let color = if hovered == Some((row, step)) { theme.hovered_menu_item } else { theme.menu_item };
+                rectangle(color, button, context.transform, g2d);
+                let sign_width = glyphs.width(text_size, sign).unwrap();
+                draw_text(sign, button[0] + (button[2] - sign_width) / 2.0, text_y, text_size, theme.text, context, g2d, glyphs);
+            }
+
+            let value = setting.value(settings);
+            let value_width = glyphs.width(text_size, &value).unwrap();
+            let value_x = (less[0] + less[2] + more[0] - value_width) / 2.0;
+            draw_text(&value, value_x, text_y, text_size, theme.text, context, g2d, glyphs);
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn draw_text(line: &str, x: f64, y: f64, font_size: FontSize, color: Color, context: Context, g2d: &mut G2d, glyphs: &mut Glyphs) {
+    text::Text::new_color(color, font_size)
+        .draw(line, glyphs, &DrawState::default(), context.transform.trans(x, y), g2d)
+        .unwrap();
+}
@@ -1,7 +1,12 @@
+use std::sync::atomic::AtomicBool;
 use std::sync::{Arc, Mutex, MutexGuard};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
+use crate::algorithms::Heuristic;
+use crate::cell::CellCoordinates;
 use crate::field::Field;
+use crate::run::pause;
+use crate::settings::Settings;
 
 #[derive(Clone)]
 pub struct SharedState(pub Arc<Mutex<State>>);
@@ -12,26 +17,96 @@ impl SharedState {
     }
 
     pub fn get(&self) -> MutexGuard<'_, State> {
-        return self.0.lock().unwrap();
+        self.0.lock().unwrap()
     }
 
-    pub fn wait(&self, ms: f64) {
-        let delay = ms * 1000.0 / self.get().speed;
-        std::thread::sleep(Duration::from_micros(delay as u64));
+    //Pause between search steps, so the search can be watched. It ends early on cancel
+    pub fn wait(&self, should_stop: &AtomicBool) {
+        let delay = {
+            let state = self.get();
+            state.step_delay * 1000.0 / state.speed
+        };
+        // headless runs have no delay
+        if delay > 0.0 {
+            pause(Duration::from_micros(delay as u64), should_stop);
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct SearchStats {
+    pub expanded: usize,
+    pub frontier: usize,
+    // g and f values of the last expanded cell, for algorithms that have them
+    pub current_g: Option<i32>,
+    pub current_f: Option<i32>,
+    pub path_length: Option<usize>,
+    pub path_cost: Option<i32>,
+    started: Option<Instant>,
+    finished: Option<Duration>,
+}
+
+impl SearchStats {
+    //Returns the expansion index of the cell
+    pub fn expand(&mut self, frontier: usize, g: Option<i32>, f: Option<i32>) -> usize {
+        self.expanded += 1;
+        self.frontier = frontier;
+        self.current_g = g;
+        self.current_f = f;
+        self.expanded
+    }
+
+    pub fn start(&mut self) {
+        self.started = Some(Instant::now());
+    }
+
+    pub fn finish(&mut self) {
+        self.finished = Some(self.elapsed());
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        match (self.finished, self.started) {
+            (Some(finished), _) => finished,
+            (None, Some(started)) => started.elapsed(),
+            (None, None) => Duration::ZERO,
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.finished.is_some()
     }
 }
 
 pub struct State {
     field: Field,
+    // milliseconds between search steps at speed 1
+    step_delay: f64,
     speed: f64,
+    pub heuristic: Heuristic,
+    pub stats: SearchStats,
+    pub path: Vec<CellCoordinates>,
 }
 
 impl State {
-    pub fn new(field: Field, speed: f64) -> State {
-        State { field, speed }
+    pub fn new(field: Field, settings: &Settings) -> State {
+        State {
+            field,
+            step_delay: settings.step_delay,
+            speed: settings.speed,
+            heuristic: settings.heuristic,
+            stats: SearchStats::default(),
+            path: Vec::new(),
+        }
+    }
+
+    //Path cost is the sum of costs of entered cells, so the start cell is not counted
+    pub fn record_path(&mut self, path: &[CellCoordinates], cost: i32) {
+        self.stats.path_length = Some(path.len());
+        self.stats.path_cost = Some(cost);
+        self.path = path.to_vec();
     }
 
     pub fn field(&mut self) -> &mut Field {
-        return &mut self.field;
+        &mut self.field
     }
 }
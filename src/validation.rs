@@ -0,0 +1,107 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use crate::cell::CellCoordinates;
+use crate::field::Field;
+
+//Checks a found path against the field: it goes from start to end by allowed steps
+//of the field neighborhood, enters no blocked cell and costs as much as reported
+pub fn validate_path(field: &Field, path: &[CellCoordinates], cost: i32) -> Result<(), String> {
+    let (Some(&first), Some(&last)) = (path.first(), path.last()) else {
+        return Err("path is empty".to_string());
+    };
+    if first != field.start {
+        return Err(format!("path starts at ({}, {}), not at the start", first.x, first.y));
+    }
+    if last != field.end {
+        return Err(format!("path ends at ({}, {}), not at the end", last.x, last.y));
+    }
+    for &coordinates in path {
+        if coordinates.x >= field.columns() || coordinates.y >= field.rows() {
+            return Err(format!("cell ({}, {}) is out of the field", coordinates.x, coordinates.y));
+        }
+        if field.is_blocked(coordinates) {
+            return Err(format!("cell ({}, {}) is blocked", coordinates.x, coordinates.y));
+        }
+    }
+    for step in path.windows(2) {
+        let (from, to) = (step[0], step[1]);
+        if !field.passable_neighbors(from).contains(&(to.x, to.y)) {
+            return Err(format!("step from ({}, {}) to ({}, {}) is not allowed", from.x, from.y, to.x, to.y));
+        }
+    }
+    let path_cost = path_cost(field, path);
+    if path_cost != cost {
+        return Err(format!("path costs {}, but {} is reported", path_cost, cost));
+    }
+    Ok(())
+}
+
+//Sum of costs of entered cells, the start cell is not counted
+pub fn path_cost(field: &Field, path: &[CellCoordinates]) -> i32 {
+    path.iter()
+        .skip(1)
+        .map(|coordinates| field.get_cell(coordinates.x, coordinates.y).get().cost as i32)
+        .sum()
+}
+
+//Reference cost of the cheapest path from start to end, none when the end can not be reached.
+//Plain Dijkstra over coordinates, it shares no code with the visualized algorithms
+pub fn optimal_cost(field: &Field) -> Option<i32> {
+    let rows = field.rows() as usize;
+    let index = |c: CellCoordinates| c.x as usize * rows + c.y as usize;
+    let mut distances = vec![i32::MAX; field.columns() as usize * rows];
+    let mut queue = BinaryHeap::from([Reverse((0, field.start.x, field.start.y))]);
+    distances[index(field.start)] = 0;
+
+    while let Some(Reverse((distance, x, y))) = queue.pop() {
+        let current = CellCoordinates { x, y };
+        if current == field.end {
+            return Some(distance);
+        }
+        if distance > distances[index(current)] {
+            continue;
+        }
+        for (x, y) in field.passable_neighbors(current) {
+            let neighbor = CellCoordinates { x, y };
+            let new_distance = distance + field.get_cell(x, y).get().cost as i32;
+            if new_distance < distances[index(neighbor)] {
+                distances[index(neighbor)] = new_distance;
+                queue.push(Reverse((new_distance, x, y)));
+            }
+        }
+    }
+    None
+}
+
+//Verdict on a path shown after a run
+#[derive(Clone, PartialEq, Debug)]
+pub enum PathVerdict {
+    Optimal,
+    Suboptimal(i32),
+    Invalid(String),
+}
+
+impl PathVerdict {
+    pub fn check(field: &Field, path: &[CellCoordinates], cost: i32) -> PathVerdict {
+        if let Err(error) = validate_path(field, path, cost) {
+            return PathVerdict::Invalid(error);
+        }
+        match optimal_cost(field) {
+            Some(optimal) if cost > optimal => PathVerdict::Suboptimal(cost - optimal),
+            Some(optimal) if cost < optimal => {
+                PathVerdict::Invalid(format!("path is cheaper than the optimal cost {}", optimal))
+            }
+            Some(_) => PathVerdict::Optimal,
+            None => PathVerdict::Invalid("the end is not reachable".to_string()),
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        match self {
+            PathVerdict::Optimal => "valid, optimal".to_string(),
+            PathVerdict::Suboptimal(excess) => format!("valid, suboptimal by {}", excess),
+            PathVerdict::Invalid(error) => format!("invalid: {}", error),
+        }
+    }
+}
@@ -0,0 +1,41 @@
+use clap::ValueEnum;
+use piston_window::types::Color;
+
+use pathfinding_algorithms::colors::ThemeKind;
+
+#[test]
+fn cell_states_have_distinct_colors_in_every_theme() {
+    for kind in ThemeKind::value_variants() {
+        let theme = kind.theme();
+        let cells = [
+            ("empty", theme.empty_cell),
+            ("blocked", theme.blocked_cell),
+            ("start", theme.start_cell),
+            ("end", theme.end_cell),
+            ("frontier", theme.frontier_cell),
+            ("closed", theme.closed_cell),
+            ("current", theme.current_cell),
+            ("reopened", theme.reopened_cell),
+            ("chosen", theme.chosen_cell),
+        ];
+        for (n, (name, color)) in cells.iter().enumerate() {
+            for (other_name, other_color) in &cells[n + 1..] {
+                assert_ne!(color, other_color, "{}: {} and {} cells", kind.name(), name, other_name);
+            }
+        }
+    }
+}
+
+fn assert_close(color: Color, expected: Color) {
+    assert!(color.iter().zip(expected).all(|(a, b)| (a - b).abs() < 1e-6), "{:?} {:?}", color, expected);
+}
+
+#[test]
+fn cost_gradient_spans_the_cost_range_of_the_field() {
+    let theme = ThemeKind::Light.theme();
+    assert_close(theme.cost_gradient_color(5, (5, 15)), theme.low_cost);
+    assert_close(theme.cost_gradient_color(15, (5, 15)), theme.high_cost);
+    // a field of one cost, also with every cost 0
+    assert_close(theme.cost_gradient_color(0, (0, 0)), theme.low_cost);
+    assert_close(theme.cost_gradient_color(7, (7, 7)), theme.low_cost);
+}
@@ -0,0 +1,79 @@
+use std::collections::HashMap;
+use std::fs;
+
+use pathfinding_algorithms::algorithms::Heuristic;
+use pathfinding_algorithms::colors::ThemeKind;
+use pathfinding_algorithms::config::{check_map_parameters, save_values, Config, MAX_CELL_COST};
+use pathfinding_algorithms::field::Neighborhood;
+use pathfinding_algorithms::settings_page::Setting;
+
+#[test]
+fn settings_page_changes_are_loaded_from_the_config_file() {
+    let path = std::env::temp_dir().join(format!("pathfinders-{}.toml", std::process::id()));
+    let content = "# my settings\n\
+                   [grid]\n\
+                   terrain = false # flat costs\n\
+                   columns = 10 # wide enough\n\
+                   \n\
+                   [window]\n\
+                   menu_font_size = 20\n";
+    fs::write(&path, content).unwrap();
+
+    let mut settings = Config::load(&path).unwrap().settings().unwrap();
+    settings.seed = 7;
+    settings.columns = 30;
+    let mut values = Vec::new();
+    for (setting, step) in [
+        (Setting::Columns, 2),
+        (Setting::Rows, -1),
+        (Setting::WallDensity, 3),
+        (Setting::MinCost, 1),
+        (Setting::MaxCost, -4),
+        (Setting::Seed, 1),
+        (Setting::Neighborhood, 1),
+        (Setting::Heuristic, -1),
+        (Setting::Speed, 2),
+        (Setting::Theme, -1),
+    ] {
+        setting.change(&mut settings, step, &HashMap::new());
+        values.push(setting.config_entry(&settings).unwrap());
+    }
+    save_values(&path, values).unwrap();
+
+    let saved = fs::read_to_string(&path).unwrap();
+    let loaded = Config::load(&path).unwrap().settings().unwrap();
+    fs::remove_file(&path).unwrap();
+    // comments and the order of the hand written values stay
+    assert!(saved.starts_with("# my settings\n[grid]\nterrain = false # flat costs\ncolumns = 40 # wide enough\n"));
+    assert!(saved.contains("[window]\nmenu_font_size = 20\n"));
+    assert_eq!((loaded.columns, loaded.rows), (40, 25));
+    assert_eq!(loaded.wall_density, 0.4);
+    assert_eq!((loaded.min_cost, loaded.max_cost), (1, 15));
+    assert_eq!(loaded.seed, 8);
+    assert!(loaded.neighborhood == Neighborhood::Eight);
+    assert!(loaded.heuristic == Heuristic::Octile);
+    assert_eq!(loaded.speed, 4.0);
+    assert!(loaded.theme.kind == ThemeKind::ColorblindSafe);
+    // values not on the page are kept
+    assert!(!loaded.terrain);
+    assert_eq!(loaded.menu_font_size, 20);
+}
+
+#[test]
+fn settings_page_keeps_map_parameters_valid() {
+    let mut settings = Config::default().settings().unwrap();
+    Setting::Columns.change(&mut settings, -100, &HashMap::new());
+    Setting::WallDensity.change(&mut settings, 100, &HashMap::new());
+    Setting::MinCost.change(&mut settings, 100, &HashMap::new());
+    assert_eq!(settings.columns, 2);
+    assert!(settings.wall_density < 1.0);
+    assert_eq!(settings.min_cost, settings.max_cost);
+    Setting::MaxCost.change(&mut settings, i16::MAX as i32, &HashMap::new());
+    assert_eq!(settings.max_cost, MAX_CELL_COST);
+    assert!(check_map_parameters(&settings).is_ok());
+    settings.max_cost = i16::MAX;
+    assert!(check_map_parameters(&settings).is_err());
+    // seeds above i64::MAX do not fit a TOML integer
+    settings.seed = u64::MAX;
+    assert!(Setting::Seed.config_entry(&settings).is_err());
+}
@@ -0,0 +1,85 @@
+use std::fs;
+
+use clap::Parser;
+
+use pathfinding_algorithms::cli::{Cli, Command, ExperimentArgs};
+use pathfinding_algorithms::config::Config;
+use pathfinding_algorithms::experiment;
+use pathfinding_algorithms::settings::Settings;
+
+fn parse(args: &[&str]) -> Result<(Settings, ExperimentArgs), String> {
+    let cli = Cli::try_parse_from(["pathfinders", "--config", "missing.toml", "experiment"].iter().chain(args))
+        .map_err(|error| error.to_string())?;
+    let mut config = Config::load(&cli.config)?;
+    config.merge(&cli);
+    let settings = config.settings()?;
+    match cli.command {
+        Some(Command::Experiment(args)) => Ok((settings, args)),
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn sizes_and_cost_ranges_are_parsed() {
+    let (_, args) = parse(&["--sizes", "30x20,50", "--cost-ranges", "1-1,0-19"]).unwrap();
+    assert_eq!(args.sizes, vec![(30, 20), (50, 50)]);
+    assert_eq!(args.cost_ranges, vec![(1, 1), (0, 19)]);
+    assert!(parse(&["--sizes", "30y20"]).is_err());
+    assert!(parse(&["--sizes", "-3"]).is_err());
+    assert!(parse(&["--cost-ranges", "5"]).is_err());
+    assert!(parse(&["--cost-ranges", "1-x"]).is_err());
+}
+
+#[test]
+fn csv_has_a_row_for_every_run() {
+    let directory = std::env::temp_dir().join(format!("pathfinders-experiment-{}", std::process::id()));
+    fs::create_dir_all(&directory).unwrap();
+    // a comma in the file name needs quoting
+    let map = directory.join("maze, copy.txt");
+    fs::copy("assets/maps/maze.txt", &map).unwrap();
+    let output = directory.join("results.csv");
+
+    let (settings, args) = parse(&[
+        "--map-file",
+        map.to_str().unwrap(),
+        "--algorithms",
+        "bfs,dijkstra",
+        "--output",
+        output.to_str().unwrap(),
+    ])
+    .unwrap();
+    experiment::run(settings, &args).unwrap();
+    let csv = fs::read_to_string(&output).unwrap();
+
+    let (settings, args) = parse(&[
+        "--sizes", "8", "--cost-ranges", "0-2", "--maps", "30", "--algorithms", "gbfs,dfs",
+        "--output", output.to_str().unwrap(),
+    ])
+    .unwrap();
+    experiment::run(settings, &args).unwrap();
+    let generated = fs::read_to_string(&output).unwrap();
+    fs::remove_dir_all(&directory).unwrap();
+
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(
+        lines[0],
+        "map,columns,rows,wall_density,min_cost,max_cost,seed,algorithm,found,valid,\
+         path_length,path_cost,optimal_cost,optimality_gap,expanded,runtime_ms"
+    );
+    assert_eq!(lines.len(), 3);
+    let quoted = format!("\"{}\",", map.display());
+    for (line, algorithm) in lines[1..].iter().zip(["BFS", "DIJKSTRA"]) {
+        let fields: Vec<&str> = line.strip_prefix(&quoted).unwrap().split(',').collect();
+        assert_eq!(fields.len(), 15, "{}", line);
+        // density, costs and seed are empty for map files
+        assert_eq!(fields[2..6], ["", "", "", ""]);
+        assert_eq!(fields[6], algorithm);
+        assert_eq!(fields[7..9], ["true", "true"]);
+    }
+    assert!(lines[2].contains(",0.0000,"), "DIJKSTRA is optimal: {}", lines[2]);
+
+    // optimal costs of 0 give no infinite gaps
+    assert_eq!(generated.lines().count(), 61);
+    assert!(!generated.contains("inf"));
+    assert!(generated.lines().skip(1).all(|line| line.split(',').count() == 16));
+}
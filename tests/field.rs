@@ -0,0 +1,56 @@
+use pathfinding_algorithms::cell::CellState;
+use pathfinding_algorithms::field::Field;
+use pathfinding_algorithms::settings::Settings;
+
+//State and cost of every cell, column by column
+fn snapshot(field: &Field) -> Vec<(CellState, i16)> {
+    field
+        .cells
+        .iter()
+        .flatten()
+        .map(|tile| {
+            let cell = tile.get();
+            (*cell.get_state(), cell.cost)
+        })
+        .collect()
+}
+
+fn settings(seed: u64, terrain: bool) -> Settings {
+    let mut settings = Settings::new(1, 30, 20);
+    settings.seed = seed;
+    settings.terrain = terrain;
+    settings.wall_density = 0.3;
+    settings.min_cost = 1;
+    settings.max_cost = 20;
+    settings
+}
+
+#[test]
+fn same_seed_gives_the_same_field() {
+    for terrain in [false, true] {
+        for seed in [0, 7, u64::MAX] {
+            let first = snapshot(&Field::generate(&settings(seed, terrain)));
+            assert_eq!(first, snapshot(&Field::generate(&settings(seed, terrain))), "seed {}", seed);
+        }
+    }
+}
+
+#[test]
+fn different_seeds_give_different_fields() {
+    for terrain in [false, true] {
+        let field = |seed| Field::generate(&settings(seed, terrain));
+        let walls = |field: &Field| {
+            field.cells.iter().flatten().map(|tile| *tile.get().get_state() == CellState::Blocked).collect::<Vec<bool>>()
+        };
+        let costs = |field: &Field| field.cells.iter().flatten().map(|tile| tile.get().cost).collect::<Vec<i16>>();
+        assert_ne!(walls(&field(1)), walls(&field(2)));
+        assert_ne!(costs(&field(1)), costs(&field(2)));
+    }
+}
+
+#[test]
+fn terrain_costs_stay_in_the_widest_cost_range() {
+    let mut field = Field::generate(&settings(3, false));
+    field.make_terrain(3, 0, i16::MAX);
+    assert!(field.cells.iter().flatten().all(|tile| tile.get().cost >= 0));
+}
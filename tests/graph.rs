@@ -0,0 +1,110 @@
+use clap::ValueEnum;
+
+use pathfinding_algorithms::algorithms::graph::{Graph, SearchObserver};
+use pathfinding_algorithms::algorithms::AlgorithmType;
+
+//Small directed road network given by edge lists
+struct Roads {
+    edges: Vec<Vec<(usize, i32)>>,
+    // straight line distances to the goal
+    estimates: Option<Vec<i32>>,
+    goal: usize,
+}
+
+impl Graph for Roads {
+    type Node = usize;
+
+    fn start(&self) -> usize {
+        0
+    }
+
+    fn goal(&self) -> usize {
+        self.goal
+    }
+
+    fn neighbors(&self, node: usize) -> Vec<(usize, i32)> {
+        self.edges[node].clone()
+    }
+
+    fn heuristic(&self, node: usize) -> Option<i32> {
+        self.estimates.as_ref().map(|estimates| estimates[node])
+    }
+}
+
+fn roads(estimates: Option<Vec<i32>>) -> Roads {
+    // 0 -> 1 -> 3 is short by edges but expensive, 0 -> 2 -> 4 -> 3 is the cheapest
+    Roads {
+        edges: vec![
+            vec![(1, 10), (2, 2)],
+            vec![(3, 10)],
+            vec![(4, 3), (1, 1)],
+            vec![],
+            vec![(3, 4)],
+            vec![(0, 1)],
+        ],
+        estimates,
+        goal: 3,
+    }
+}
+
+#[derive(Default)]
+struct Recorder {
+    expanded: Vec<usize>,
+    found: Option<(Vec<usize>, i32)>,
+}
+
+impl SearchObserver<usize> for Recorder {
+    fn expand(&mut self, node: usize, _frontier: usize, _g: Option<i32>, _f: Option<i32>) {
+        self.expanded.push(node);
+    }
+
+    fn found(&mut self, path: &[usize], cost: i32) {
+        self.found = Some((path.to_vec(), cost));
+    }
+}
+
+#[test]
+fn every_algorithm_searches_a_road_network() {
+    for estimates in [None, Some(vec![9, 8, 7, 0, 4, 10])] {
+        let graph = roads(estimates);
+        for &algorithm in AlgorithmType::value_variants() {
+            let mut recorder = Recorder::default();
+            let result = algorithm
+                .search(&graph, &mut recorder)
+                .unwrap_or_else(|| panic!("{} found no path", algorithm.name()));
+            assert_eq!(result.path.first(), Some(&0), "{}", algorithm.name());
+            assert_eq!(result.path.last(), Some(&3), "{}", algorithm.name());
+            let cost: i32 = result
+                .path
+                .windows(2)
+                .map(|step| graph.edges[step[0]].iter().find(|edge| edge.0 == step[1]).unwrap().1)
+                .sum();
+            assert_eq!(result.cost, cost, "{}", algorithm.name());
+            assert_eq!(recorder.found, Some((result.path.clone(), result.cost)), "{}", algorithm.name());
+            assert_eq!(recorder.expanded.last(), Some(&3), "{}", algorithm.name());
+            // node 5 can not be reached from the start
+            assert!(!recorder.expanded.contains(&5), "{}", algorithm.name());
+        }
+    }
+}
+
+#[test]
+fn cost_aware_algorithms_find_the_cheapest_route() {
+    for estimates in [None, Some(vec![9, 8, 7, 0, 4, 10])] {
+        let graph = roads(estimates);
+        for algorithm in [AlgorithmType::Dijkstra, AlgorithmType::AStar] {
+            let result = algorithm.search(&graph, &mut ()).unwrap();
+            assert_eq!(result.path, vec![0, 2, 4, 3], "{}", algorithm.name());
+            assert_eq!(result.cost, 9, "{}", algorithm.name());
+        }
+    }
+}
+
+#[test]
+fn unreachable_goal_gives_no_path() {
+    let mut graph = roads(None);
+    graph.goal = 5;
+    for &algorithm in AlgorithmType::value_variants() {
+        assert!(algorithm.search(&graph, &mut ()).is_none(), "{}", algorithm.name());
+    }
+}
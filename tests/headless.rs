@@ -0,0 +1,39 @@
+use std::fs;
+use std::path::Path;
+
+use pathfinding_algorithms::algorithms::AlgorithmType;
+use pathfinding_algorithms::headless::solve;
+use pathfinding_algorithms::settings::Settings;
+
+#[test]
+fn report_of_a_found_path() {
+    let settings = Settings::new(30, 30, 30);
+    let report = solve(settings, AlgorithmType::Dijkstra, Some(Path::new("assets/maps/maze.txt"))).unwrap();
+    assert!(report.found);
+    assert_eq!(report.map.as_deref(), Some("assets/maps/maze.txt"));
+    assert_eq!(report.seed, None);
+    assert_eq!(report.path_length, Some(report.path.len()));
+    assert_eq!(report.path_cost, report.optimal_cost);
+    assert_eq!(report.verdict.as_deref(), Some("valid, optimal"));
+    let json = serde_json::to_value(&report).unwrap();
+    assert_eq!(json["found"], true);
+    assert_eq!(json["algorithm"], "DIJKSTRA");
+}
+
+#[test]
+fn report_of_an_unreachable_end() {
+    let path = std::env::temp_dir().join(format!("pathfinders-walled-{}.txt", std::process::id()));
+    fs::write(&path, "S....\n.....\n...##\n...#E\n").unwrap();
+    let report = solve(Settings::new(30, 5, 4), AlgorithmType::AStar, Some(&path));
+    fs::remove_file(&path).unwrap();
+
+    let report = report.unwrap();
+    assert!(!report.found);
+    assert!(report.path.is_empty());
+    assert_eq!((report.path_length, report.path_cost, report.optimal_cost), (None, None, None));
+    assert_eq!(report.verdict, None);
+    assert_eq!((report.columns, report.rows), (5, 4));
+
+    let error = solve(Settings::new(30, 5, 4), AlgorithmType::AStar, Some(Path::new("missing.txt")));
+    assert!(error.err().unwrap().starts_with("missing.txt"));
+}
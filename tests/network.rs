@@ -0,0 +1,102 @@
+use std::path::Path;
+
+use clap::ValueEnum;
+
+use pathfinding_algorithms::algorithms::graph::Graph;
+use pathfinding_algorithms::algorithms::AlgorithmType;
+use pathfinding_algorithms::network::{load_network, parse_dimacs, parse_edge_list};
+
+const GRAPH: &str = "c two routes from 1 to 4\n\
+                     p sp 4 5\n\
+                     a 1 2 3\n\
+                     a 2 4 3\n\
+                     a 1 3 1\n\
+                     a 3 4 7\n\
+                     a 4 1 5\n";
+
+const COORDINATES: &str = "p aux sp co 4\n\
+                           v 1 0 0\n\
+                           v 2 3 0\n\
+                           v 3 0 1\n\
+                           v 4 3 3\n";
+
+#[test]
+fn dimacs_graph_is_searched_by_every_algorithm() {
+    let network = parse_dimacs(GRAPH, Some(COORDINATES)).unwrap();
+    assert_eq!(network.names.len(), 4);
+    assert_eq!(network.edge_count(), 5);
+    assert_eq!((network.start, network.goal), (0, 3));
+    for &algorithm in AlgorithmType::value_variants() {
+        let result = algorithm.search(&network, &mut ()).unwrap();
+        assert_eq!(result.path.first(), Some(&0), "{}", algorithm.name());
+        assert_eq!(result.path.last(), Some(&3), "{}", algorithm.name());
+    }
+    for algorithm in [AlgorithmType::Dijkstra, AlgorithmType::AStar] {
+        let result = algorithm.search(&network, &mut ()).unwrap();
+        assert_eq!(result.path, vec![0, 1, 3], "{}", algorithm.name());
+        assert_eq!(result.cost, 6, "{}", algorithm.name());
+    }
+}
+
+#[test]
+fn straight_line_heuristic_never_overestimates() {
+    let network = parse_dimacs(GRAPH, Some(COORDINATES)).unwrap();
+    // cheapest arcs cost as much as their length, so the estimate is the straight distance
+    assert_eq!(network.heuristic(0), Some(4));
+    assert_eq!(network.heuristic(1), Some(3));
+    assert_eq!(network.heuristic(3), Some(0));
+    // without coordinates nodes are placed on a circle and there is no heuristic
+    assert_eq!(parse_dimacs(GRAPH, None).unwrap().heuristic(0), None);
+}
+
+#[test]
+fn invalid_dimacs_files_are_rejected() {
+    assert!(parse_dimacs("a 1 2 3\n", None).is_err());
+    assert!(parse_dimacs("p sp 2 1\na 1 3 3\n", None).is_err());
+    assert!(parse_dimacs("p sp 2 1\na 1 2 -3\n", None).is_err());
+    assert!(parse_dimacs("p sp 2 2\na 1 2 3\n", None).is_err());
+    assert!(parse_dimacs("p sp 2 1\nx 1 2 3\n", None).is_err());
+    // every node needs coordinates once they are given
+    assert!(parse_dimacs("p sp 2 1\na 1 2 3\n", Some("v 1 0 0\n")).is_err());
+}
+
+#[test]
+fn edge_list_uses_node_ids_from_the_file() {
+    let mut network = parse_edge_list(
+        "# roads between places\n\
+         \n\
+         from,to,cost\n\
+         # comment\n\
+         home, shop, 4\n\
+         shop,work,4\n\
+         home,work,10\n",
+        Some("# positions\nid,x,y\nhome,0,0\nshop,1,1\nwork,2,0\n"),
+    )
+    .unwrap();
+    assert_eq!(network.names, ["home", "shop", "work"]);
+    network.set_endpoints(Some("home"), Some("work")).unwrap();
+    let result = AlgorithmType::AStar.search(&network, &mut ()).unwrap();
+    assert_eq!(result.path, vec![0, 1, 2]);
+    assert_eq!(result.cost, 8);
+
+    assert!(network.set_endpoints(Some("school"), None).is_err());
+    assert!(parse_edge_list("a,b\n", None).is_err());
+    assert!(parse_edge_list("a,b,1\nb,c,x\n", None).is_err());
+    assert!(parse_edge_list("from,to,cost\nfrom,to,cost\na,b,1\n", None).is_err());
+    assert!(parse_edge_list("a,b,1\n", Some("c,0,0\n")).is_err());
+    assert!(parse_edge_list("", None).is_err());
+}
+
+#[test]
+fn bundled_graphs_are_solvable() {
+    for (graph, coordinates) in [
+        ("assets/graphs/town.gr", "assets/graphs/town.co"),
+        ("assets/graphs/cities.csv", "assets/graphs/cities_coordinates.csv"),
+    ] {
+        let network = load_network(Path::new(graph), Some(Path::new(coordinates))).unwrap();
+        let optimal = AlgorithmType::Dijkstra.search(&network, &mut ()).unwrap();
+        let result = AlgorithmType::AStar.search(&network, &mut ()).unwrap();
+        assert_eq!(result.cost, optimal.cost, "{}", graph);
+    }
+    assert!(load_network(Path::new("assets/maps/maze.txt"), None).is_err());
+}
@@ -0,0 +1,194 @@
+use clap::ValueEnum;
+
+use pathfinding_algorithms::algorithms::{AlgorithmType, Heuristic};
+use pathfinding_algorithms::cell::CellCoordinates;
+use pathfinding_algorithms::field::{Field, Neighborhood};
+use pathfinding_algorithms::headless::search;
+use pathfinding_algorithms::scenario::{parse_map, SCENARIOS};
+use pathfinding_algorithms::settings::Settings;
+use pathfinding_algorithms::validation::{optimal_cost, validate_path};
+
+const SEEDS: std::ops::Range<u64> = 0..8;
+
+fn settings(seed: u64, neighborhood: Neighborhood) -> Settings {
+    let mut settings = Settings::new(1, 20, 15);
+    settings.seed = seed;
+    settings.ensure_solvable = true;
+    settings.neighborhood = neighborhood;
+    settings
+}
+
+//Path and its reported cost, none when nothing is found
+fn run(settings: &Settings, algorithm: AlgorithmType, field: &Field) -> Option<(Vec<CellCoordinates>, i32)> {
+    let state = search(settings, algorithm, field.duplicate());
+    let state = state.get();
+    state.stats.path_cost.map(|cost| (state.path.clone(), cost))
+}
+
+fn coordinates(x: u16, y: u16) -> CellCoordinates {
+    CellCoordinates { x, y }
+}
+
+#[test]
+fn every_algorithm_returns_valid_paths() {
+    for neighborhood in [Neighborhood::Four, Neighborhood::Eight] {
+        for seed in SEEDS {
+            let settings = settings(seed, neighborhood);
+            let field = Field::generate(&settings);
+            for &algorithm in AlgorithmType::value_variants() {
+                let (path, cost) = run(&settings, algorithm, &field)
+                    .unwrap_or_else(|| panic!("{} found no path on seed {}", algorithm.name(), seed));
+                if let Err(error) = validate_path(&field, &path, cost) {
+                    panic!("{} on seed {}: {}", algorithm.name(), seed, error);
+                }
+                assert!(cost >= optimal_cost(&field).unwrap(), "{} beats the oracle", algorithm.name());
+            }
+        }
+    }
+}
+
+#[test]
+fn dijkstra_paths_are_optimal() {
+    for neighborhood in [Neighborhood::Four, Neighborhood::Eight] {
+        for seed in SEEDS {
+            let settings = settings(seed, neighborhood);
+            let field = Field::generate(&settings);
+            let (_, cost) = run(&settings, AlgorithmType::Dijkstra, &field).unwrap();
+            assert_eq!(Some(cost), optimal_cost(&field), "seed {}", seed);
+        }
+    }
+}
+
+#[test]
+fn a_star_paths_are_optimal_with_admissible_heuristics() {
+    // default costs start at 0, the heuristic is scaled by the cheapest cell
+    for (neighborhood, heuristic) in [
+        (Neighborhood::Four, Heuristic::Manhattan),
+        (Neighborhood::Four, Heuristic::Octile),
+        (Neighborhood::Eight, Heuristic::Chebyshev),
+    ] {
+        for (seed, min_cost) in SEEDS.zip([0, 5].into_iter().cycle()) {
+            let mut settings = settings(seed, neighborhood);
+            settings.min_cost = min_cost;
+            settings.heuristic = heuristic;
+            let field = Field::generate(&settings);
+            let (_, cost) = run(&settings, AlgorithmType::AStar, &field).unwrap();
+            assert_eq!(Some(cost), optimal_cost(&field), "seed {}", seed);
+        }
+    }
+}
+
+#[test]
+fn bfs_paths_are_optimal_on_uniform_costs() {
+    for seed in SEEDS {
+        let mut settings = settings(seed, Neighborhood::Four);
+        settings.min_cost = 1;
+        settings.max_cost = 1;
+        let field = Field::generate(&settings);
+        let (_, cost) = run(&settings, AlgorithmType::BFS, &field).unwrap();
+        assert_eq!(Some(cost), optimal_cost(&field), "seed {}", seed);
+    }
+}
+
+#[test]
+fn every_algorithm_solves_bundled_maps() {
+    let settings = settings(0, Neighborhood::Four);
+    for scenario in SCENARIOS.iter() {
+        let field = scenario.build();
+        let optimal = optimal_cost(&field).unwrap_or_else(|| panic!("{} is not solvable", scenario.name));
+        for &algorithm in AlgorithmType::value_variants() {
+            let (path, cost) = run(&settings, algorithm, &field)
+                .unwrap_or_else(|| panic!("{} found no path on {}", algorithm.name(), scenario.name));
+            if let Err(error) = validate_path(&field, &path, cost) {
+                panic!("{} on {}: {}", algorithm.name(), scenario.name, error);
+            }
+            if let AlgorithmType::Dijkstra = algorithm {
+                assert_eq!(cost, optimal, "{}", scenario.name);
+            }
+        }
+    }
+}
+
+#[test]
+fn no_algorithm_finds_a_path_to_a_walled_end() {
+    let field = parse_map(
+        "S....\n\
+         .....\n\
+         ...##\n\
+         ...#E\n",
+    )
+    .unwrap();
+    assert_eq!(optimal_cost(&field), None);
+    let settings = settings(0, Neighborhood::Four);
+    for &algorithm in AlgorithmType::value_variants() {
+        assert!(run(&settings, algorithm, &field).is_none(), "{}", algorithm.name());
+    }
+}
+
+#[test]
+fn validate_path_rejects_broken_paths() {
+    let mut field = parse_map(
+        "S.,.\n\
+         .#..\n\
+         ...E\n",
+    )
+    .unwrap();
+    let valid = [
+        coordinates(0, 0),
+        coordinates(1, 0),
+        coordinates(2, 0),
+        coordinates(3, 0),
+        coordinates(3, 1),
+        coordinates(3, 2),
+    ];
+    assert_eq!(validate_path(&field, &valid, 9), Ok(()));
+    assert_eq!(optimal_cost(&field), Some(5));
+
+    assert!(validate_path(&field, &[], 0).is_err());
+    // wrong cost
+    assert!(validate_path(&field, &valid, 8).is_err());
+    // does not start at the start
+    assert!(validate_path(&field, &valid[1..], 3).is_err());
+    // does not reach the end
+    assert!(validate_path(&field, &valid[..5], 8).is_err());
+    // jumps over a cell
+    let gap = [coordinates(0, 0), coordinates(2, 0), coordinates(3, 0), coordinates(3, 1), coordinates(3, 2)];
+    assert!(validate_path(&field, &gap, 4).is_err());
+    // goes through a blocked cell
+    let blocked = [
+        coordinates(0, 0),
+        coordinates(0, 1),
+        coordinates(1, 1),
+        coordinates(2, 1),
+        coordinates(3, 1),
+        coordinates(3, 2),
+    ];
+    assert!(validate_path(&field, &blocked, 5).is_err());
+
+    // diagonal steps are allowed only with eight neighbors and never cut a blocked corner
+    let diagonal = [coordinates(0, 0), coordinates(1, 0), coordinates(2, 0), coordinates(2, 1), coordinates(3, 2)];
+    assert!(validate_path(&field, &diagonal, 8).is_err());
+    field.neighborhood = Neighborhood::Eight;
+    assert_eq!(validate_path(&field, &diagonal, 8), Ok(()));
+    let corner_cut = [coordinates(0, 0), coordinates(1, 0), coordinates(2, 1), coordinates(3, 2)];
+    assert!(validate_path(&field, &corner_cut, 3).is_err());
+}
+
+#[test]
+fn cost_aware_algorithms_sum_costs_along_the_path() {
+    // ranking cells by the cost of the last step alone, as the first versions did, gives 24 (DIJKSTRA)
+    // and 26 (A_STAR) here
+    let field = parse_map(
+        "S~.,~\n\
+         ,~...\n\
+         ..~,E\n",
+    )
+    .unwrap();
+    assert_eq!(optimal_cost(&field), Some(20));
+    let settings = settings(0, Neighborhood::Four);
+    for algorithm in [AlgorithmType::Dijkstra, AlgorithmType::AStar] {
+        let (path, cost) = run(&settings, algorithm, &field).unwrap();
+        assert_eq!(cost, 20, "{}", algorithm.name());
+        validate_path(&field, &path, cost).unwrap();
+    }
+}
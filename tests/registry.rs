@@ -0,0 +1,18 @@
+use pathfinding_algorithms::algorithms::registry::{ALGORITHMS, ALGORITHM_TYPES};
+
+#[test]
+fn every_algorithm_type_is_registered_once() {
+    for algorithm_type in ALGORITHM_TYPES {
+        let entries = ALGORITHMS.iter().filter(|info| info.algorithm_type == algorithm_type).count();
+        assert_eq!(entries, 1, "{:?}", algorithm_type);
+        assert!(algorithm_type.info().algorithm_type == algorithm_type);
+    }
+}
+
+#[test]
+fn registry_ids_and_names_are_unique() {
+    for (index, info) in ALGORITHMS.iter().enumerate() {
+        assert!(ALGORITHMS[..index].iter().all(|other| other.id != info.id), "{}", info.id);
+        assert!(ALGORITHMS[..index].iter().all(|other| other.name != info.name), "{}", info.name);
+    }
+}
@@ -0,0 +1,62 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use pathfinding_algorithms::run::{pause, RunManager, RunStatus, SearchRun};
+
+//Run that sleeps in steps until it is cancelled or the steps are over
+fn run(name: &str, steps: u32) -> SearchRun {
+    let should_stop = Arc::new(AtomicBool::new(false));
+    let flag = Arc::clone(&should_stop);
+    SearchRun::spawn("test", name, should_stop, move || {
+        for _ in 0..steps {
+            pause(Duration::from_millis(50), &flag);
+            if flag.load(Ordering::SeqCst) {
+                return;
+            }
+        }
+    })
+}
+
+#[test]
+fn stopped_runs_report_how_they_ended() {
+    let mut manager = RunManager::new(Duration::from_secs(5));
+    let finished = run("quick", 0);
+    while !finished.is_finished() {
+        thread::sleep(Duration::from_millis(1));
+    }
+    let should_stop = Arc::new(AtomicBool::new(false));
+    let panicked = SearchRun::spawn("test", "broken", should_stop, || panic!("search failed"));
+    while !panicked.is_finished() {
+        thread::sleep(Duration::from_millis(1));
+    }
+
+    let started = Instant::now();
+    let statuses = manager.stop(vec![finished, run("slow", 1000), panicked]);
+    // the sleeping run wakes up on cancel instead of finishing its steps
+    assert!(started.elapsed() < Duration::from_secs(1));
+    assert_eq!(
+        statuses,
+        vec![
+            ("quick".to_string(), RunStatus::Finished),
+            ("slow".to_string(), RunStatus::Cancelled),
+            ("broken".to_string(), RunStatus::Panicked),
+        ]
+    );
+    assert_eq!(manager.last_report.as_deref(), Some("quick finished, slow cancelled, broken failed"));
+    assert_eq!(manager.lingering(), 0);
+}
+
+#[test]
+fn runs_ignoring_the_stop_flag_are_kept_until_they_end() {
+    let mut manager = RunManager::new(Duration::from_millis(10));
+    let should_stop = Arc::new(AtomicBool::new(false));
+    let stubborn = SearchRun::spawn("test", "stubborn", should_stop, || thread::sleep(Duration::from_millis(300)));
+
+    let statuses = manager.stop(vec![stubborn]);
+    assert_eq!(statuses, vec![("stubborn".to_string(), RunStatus::TimedOut)]);
+    assert_eq!(manager.lingering(), 1);
+    thread::sleep(Duration::from_millis(500));
+    assert_eq!(manager.lingering(), 0);
+}
@@ -0,0 +1,20 @@
+use pathfinding_algorithms::config::MAX_CELLS_NUMBER;
+use pathfinding_algorithms::scenario::parse_map;
+
+#[test]
+fn parse_map_rejects_maps_out_of_the_field_bounds() {
+    assert!(parse_map("SE\n").is_err());
+    assert!(parse_map("S\nE\n").is_err());
+    let wide = format!("S{}E\n{}\n", ".".repeat(MAX_CELLS_NUMBER as usize - 1), ".".repeat(MAX_CELLS_NUMBER as usize + 1));
+    assert!(parse_map(&wide).is_err());
+    // u16::MAX + 1 rows used to wrap around to 0
+    let tall = format!("S.\n{}.E\n", "..\n".repeat(u16::MAX as usize - 1));
+    assert!(parse_map(&tall).is_err());
+    assert!(parse_map("S.\n.E\n").is_ok());
+}
+
+#[test]
+fn parse_map_rejects_a_second_start_or_end() {
+    assert!(matches!(parse_map("S.S\n..E\n"), Err(error) if error.contains("start")));
+    assert!(matches!(parse_map("S.E\n..E\n"), Err(error) if error.contains("end")));
+}